@@ -0,0 +1,143 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#![allow(unused)]
+
+#[cfg(target_os = "linux")]
+fn main() {
+    use gtk4::prelude::*;
+    use muda::{
+        accelerator::{Accelerator, Code, Modifiers},
+        AboutMetadata, CheckMenuItem, ContextMenu, IconMenuItem, Menu, MenuEvent, MenuItem,
+        PredefinedMenuItem, Submenu,
+    };
+
+    let app = gtk4::Application::new(Some("app.muda.gtk4"), Default::default());
+
+    app.connect_activate(|app| {
+        let menu_bar = Menu::new();
+
+        let file_m = Submenu::new("&File", true);
+        let edit_m = Submenu::new("&Edit", true);
+
+        menu_bar.append_items(&[&file_m, &edit_m]).unwrap();
+
+        let custom_i_1 = MenuItem::new(
+            "C&ustom 1",
+            true,
+            Some(Accelerator::new(Some(Modifiers::ALT), Code::KeyC)),
+        );
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/icon.png");
+        let icon = load_icon(std::path::Path::new(path));
+        let image_item = IconMenuItem::new(
+            "Image custom 1",
+            true,
+            Some(icon),
+            Some(Accelerator::new(Some(Modifiers::CONTROL), Code::KeyI)),
+        );
+
+        let check_custom_i_1 = CheckMenuItem::new("Check Custom 1", true, true, None);
+        let check_custom_i_2 = CheckMenuItem::new(
+            "Check Custom 2",
+            true,
+            false,
+            Some(Accelerator::new(Some(Modifiers::SHIFT), Code::KeyD)),
+        );
+
+        file_m
+            .append_items(&[
+                &custom_i_1,
+                &image_item,
+                &PredefinedMenuItem::separator(),
+                &check_custom_i_1,
+                &check_custom_i_2,
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::about(
+                    None,
+                    Some(AboutMetadata {
+                        name: Some("gtk4".to_string()),
+                        version: Some("1.2.3".to_string()),
+                        copyright: Some("Copyright gtk4".to_string()),
+                        ..Default::default()
+                    }),
+                ),
+            ])
+            .unwrap();
+
+        edit_m
+            .append_items(&[
+                &PredefinedMenuItem::copy(None),
+                &PredefinedMenuItem::cut(None),
+                &PredefinedMenuItem::paste(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::select_all(None),
+            ])
+            .unwrap();
+
+        let window = gtk4::ApplicationWindow::builder()
+            .application(app)
+            .title("Window 1")
+            .default_width(800)
+            .default_height(600)
+            .build();
+
+        let vbox = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+        let entry = gtk4::Entry::new();
+        vbox.append(&entry);
+        window.set_child(Some(&vbox));
+
+        menu_bar.init_for_gtk_window(&window, Some(&vbox)).unwrap();
+
+        let click = gtk4::GestureClick::new();
+        click.set_button(gtk4::gdk::BUTTON_SECONDARY);
+        {
+            let window = window.clone();
+            click.connect_released(move |_, _, x, y| {
+                let position = muda::LogicalPosition { x, y };
+                file_m.show_context_menu_for_gtk_window(&window, Some(position.into()));
+            });
+        }
+        window.add_controller(click);
+
+        glib_timeout(move || {
+            if let Ok(event) = MenuEvent::receiver().try_recv() {
+                if event.id == custom_i_1.id() {
+                    custom_i_1
+                        .set_accelerator(Some(Accelerator::new(Some(Modifiers::SHIFT), Code::KeyF)))
+                        .unwrap();
+                }
+                println!("{event:?}");
+            }
+        });
+
+        window.present();
+    });
+
+    app.run();
+}
+
+#[cfg(target_os = "linux")]
+fn glib_timeout<F: Fn() + 'static>(f: F) {
+    gtk4::glib::timeout_add_local(std::time::Duration::from_millis(16), move || {
+        f();
+        gtk4::glib::ControlFlow::Continue
+    });
+}
+
+#[cfg(target_os = "linux")]
+fn load_icon(path: &std::path::Path) -> muda::icon::Icon {
+    let (icon_rgba, icon_width, icon_height) = {
+        let image = image::open(path)
+            .expect("Failed to open icon path")
+            .into_rgba8();
+        let (width, height) = image.dimensions();
+        let rgba = image.into_raw();
+        (rgba, width, height)
+    };
+    muda::icon::Icon::from_rgba(icon_rgba, icon_width, icon_height).expect("Failed to open icon")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
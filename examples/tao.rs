@@ -10,7 +10,7 @@ use muda::{
 };
 #[cfg(target_os = "macos")]
 use tao::platform::macos::WindowExtMacOS;
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 use tao::platform::unix::WindowExtUnix;
 #[cfg(target_os = "windows")]
 use tao::platform::windows::{EventLoopBuilderExtWindows, WindowExtWindows};
@@ -135,7 +135,7 @@ fn main() {
         menu_bar.init_for_hwnd(window.hwnd() as _);
         menu_bar.init_for_hwnd(window2.hwnd() as _);
     }
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     {
         menu_bar.init_for_gtk_window(window.gtk_window(), window.default_vbox());
         menu_bar.init_for_gtk_window(window2.gtk_window(), window2.default_vbox());
@@ -212,7 +212,7 @@ fn show_context_menu(window: &Window, menu: &dyn ContextMenu, position: Option<P
     println!("Show context menu at position {position:?}");
     #[cfg(target_os = "windows")]
     menu.show_context_menu_for_hwnd(window.hwnd() as _, position);
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     menu.show_context_menu_for_gtk_window(window.gtk_window(), position);
     #[cfg(target_os = "macos")]
     menu.show_context_menu_for_nsview(window.ns_view() as _, position);
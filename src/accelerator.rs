@@ -26,9 +26,19 @@
 //! # assert!("shift+KeyQ+alt".parse::<Accelerator>().is_err());
 //! ```
 //!
+//! Keys are physical keys by default, named after their position on a US keyboard layout.
+//! A character in single quotes is a logical key instead, triggered by whichever key produces
+//! this character in the current keyboard layout, see [`Accelerator::new_logical`].
+//! ```no_run
+//! # use muda::accelerator::{Accelerator};
+//! let physical: Accelerator = "CmdOrCtrl+KeyZ".parse().unwrap();
+//! let logical: Accelerator = "CmdOrCtrl+'z'".parse().unwrap();
+//! let plus: Accelerator = "CmdOrCtrl+'+'".parse().unwrap();
+//! ```
+//!
 
-pub use keyboard_types::{Code, Modifiers};
-use std::{borrow::Borrow, hash::Hash, str::FromStr};
+pub use keyboard_types::{Code, Key, Modifiers};
+use std::{borrow::Borrow, fmt, hash::Hash, ops::Range, str::FromStr};
 
 #[cfg(target_os = "macos")]
 pub const CMD_OR_CTRL: Modifiers = Modifiers::SUPER;
@@ -38,27 +48,142 @@ pub const CMD_OR_CTRL: Modifiers = Modifiers::CONTROL;
 /// A keyboard shortcut that consists of an optional combination
 /// of modifier keys (provided by [`Modifiers`](crate::accelerator::Modifiers)) and
 /// one key ([`Code`](crate::accelerator::Code)).
+///
+/// ## Platform-specific:
+///
+/// - **macOS:** Caps Lock, media, browser, launch, international and IME keys
+///   can't be menu key equivalents and are reported as [`crate::Error::UnrecognizedAcceleratorCode`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub struct Accelerator {
     pub(crate) mods: Modifiers,
     pub(crate) key: Code,
+    pub(crate) logical: Option<char>,
+    pub(crate) sides: ModifierSides,
     id: u32,
 }
 
+/// The modifiers that have to be pressed on a specific side of the keyboard,
+/// for example the left [`Modifiers::CONTROL`] but not the right one.
+///
+/// Modifiers in neither set can be pressed on either side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ModifierSides {
+    /// Modifiers that have to be pressed on the left side.
+    pub left: Modifiers,
+    /// Modifiers that have to be pressed on the right side.
+    pub right: Modifiers,
+}
+
+impl ModifierSides {
+    /// Returns `true` if no modifier has to be pressed on a specific side.
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
+    }
+}
+
+/// Modifiers that [`Accelerator::with_sides`] can require on one side, with their canonical names.
+const SIDED_MODIFIERS: [(Modifiers, &str); 4] = [
+    (Modifiers::CONTROL, "Ctrl"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::SHIFT, "Shift"),
+    (Modifiers::SUPER, "Super"),
+];
+
+/// Modifiers that only [`Accelerator::with_modifiers`] adds, with their canonical names.
+const EXTRA_MODIFIERS: [(Modifiers, &str); 3] = [
+    (Modifiers::HYPER, "Hyper"),
+    (Modifiers::META, "Meta"),
+    (Modifiers::ALT_GRAPH, "AltGr"),
+];
+
 impl Accelerator {
     /// Creates a new accelerator to define keyboard shortcuts throughout your application.
     /// Only [`Modifiers::ALT`], [`Modifiers::SHIFT`], [`Modifiers::CONTROL`], and [`Modifiers::SUPER`]
+    /// are commonly supported, [`Modifiers::META`] is treated as [`Modifiers::SUPER`],
+    /// see [`Accelerator::with_modifiers`] to keep it apart.
     pub fn new(mods: Option<Modifiers>, key: Code) -> Self {
-        let mut mods = mods.unwrap_or_else(Modifiers::empty);
-        if mods.contains(Modifiers::META) {
-            mods.remove(Modifiers::META);
-            mods.insert(Modifiers::SUPER);
-        }
-        let mut accelerator = Self { mods, key, id: 0 };
+        let mut accelerator = Self {
+            mods: normalize_modifiers(mods),
+            key,
+            logical: None,
+            sides: ModifierSides::default(),
+            id: 0,
+        };
         accelerator.generate_hash();
         accelerator
     }
 
+    /// Creates an accelerator triggered by the key that produces the character `key`
+    /// in the current keyboard layout, instead of a physical key like [`Accelerator::new`].
+    ///
+    /// This makes `Ctrl+Z` follow the key labeled `Z` on AZERTY or QWERTZ layouts,
+    /// and `Ctrl+/` reachable wherever the layout puts `/`.
+    /// Letters are case insensitive, add [`Modifiers::SHIFT`] to require shift.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows:** The key is looked up in the keyboard layout that is active when the accelerator is registered.
+    /// - **Linux:** gtk looks the key up in the current keymap whenever a key is pressed.
+    pub fn new_logical(mods: Option<Modifiers>, key: char) -> Self {
+        let mut lowercase = key.to_lowercase();
+        let key = match (lowercase.next(), lowercase.next()) {
+            (Some(c), None) => c,
+            _ => key,
+        };
+
+        let mut accelerator = Self {
+            mods: normalize_modifiers(mods),
+            key: Code::Unidentified,
+            logical: Some(key),
+            sides: ModifierSides::default(),
+            id: 0,
+        };
+        accelerator.generate_hash();
+        accelerator
+    }
+
+    /// Adds `mods` to this accelerator as they are, unlike [`Accelerator::new`] this keeps
+    /// [`Modifiers::META`] apart from [`Modifiers::SUPER`], so shortcuts can use
+    /// [`Modifiers::HYPER`], [`Modifiers::META`] and [`Modifiers::ALT_GRAPH`] as additional modifiers.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows:** [`Modifiers::ALT_GRAPH`] is `Ctrl+Alt`, [`Modifiers::HYPER`] and [`Modifiers::META`]
+    ///   are not supported and are reported as [`crate::Error::UnsupportedAcceleratorModifiers`].
+    /// - **macOS:** [`Modifiers::ALT_GRAPH`] is `Option`, [`Modifiers::HYPER`] and [`Modifiers::META`]
+    ///   are not supported and are reported as [`crate::Error::UnsupportedAcceleratorModifiers`].
+    /// - **Linux(gtk4):** [`Modifiers::ALT_GRAPH`] is not supported and is reported as
+    ///   [`crate::Error::UnsupportedAcceleratorModifiers`].
+    pub fn with_modifiers(mut self, mods: Modifiers) -> Self {
+        self.mods.insert(mods);
+        self.generate_hash();
+        self
+    }
+
+    /// Requires the modifiers of `sides` to be pressed on a specific side of the keyboard,
+    /// they are added to the modifiers of this accelerator.
+    ///
+    /// Native menus and [`Menu::handle_key`](crate::Menu::handle_key) can't tell both sides apart
+    /// and trigger the accelerator with either of them, use [`Accelerator::matches_with_sides`]
+    /// to handle key events yourself.
+    pub fn with_sides(mut self, sides: ModifierSides) -> Self {
+        self.mods.insert(sides.left | sides.right);
+        self.sides.left.insert(sides.left);
+        self.sides.right.insert(sides.right);
+        self.generate_hash();
+        self
+    }
+
+    /// Returns the modifiers that have to be pressed on a specific side of the keyboard.
+    pub fn sides(&self) -> ModifierSides {
+        self.sides
+    }
+
+    /// Returns the character of this accelerator if it was created with [`Accelerator::new_logical`].
+    pub fn logical_key(&self) -> Option<char> {
+        self.logical
+    }
+
     fn generate_hash(&mut self) {
         let mut str = String::new();
         if self.mods.contains(Modifiers::SHIFT) {
@@ -73,7 +198,22 @@ impl Accelerator {
         if self.mods.contains(Modifiers::SUPER) {
             str.push_str("super+")
         }
-        str.push_str(&self.key.to_string());
+        for (modifier, name) in EXTRA_MODIFIERS {
+            if self.mods.contains(modifier) {
+                str.push_str(&format!("{}+", name.to_lowercase()));
+            }
+        }
+        for (side, mods) in [("left", self.sides.left), ("right", self.sides.right)] {
+            for (modifier, name) in SIDED_MODIFIERS {
+                if mods.contains(modifier) {
+                    str.push_str(&format!("{side}{}+", name.to_lowercase()));
+                }
+            }
+        }
+        match self.logical {
+            Some(c) => str.push_str(&format!("'{c}'")),
+            None => str.push_str(&self.key.to_string()),
+        }
 
         let mut s = std::collections::hash_map::DefaultHasher::new();
         str.hash(&mut s);
@@ -87,12 +227,373 @@ impl Accelerator {
     }
 
     /// Returns `true` if this [`Code`] and [`Modifiers`] matches this `Accelerator`.
+    ///
+    /// Logical accelerators never match a [`Code`], use [`Accelerator::matches_key`] for them.
     pub fn matches(&self, modifiers: impl Borrow<Modifiers>, key: impl Borrow<Code>) -> bool {
         // Should be a const but const bit_or doesn't work here.
         let base_mods = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER;
         let modifiers = modifiers.borrow();
         let key = key.borrow();
-        self.mods == *modifiers & base_mods && self.key == *key
+        self.logical.is_none()
+            && self.mods == *modifiers & (base_mods | self.extra_modifiers())
+            && self.key == *key
+    }
+
+    /// Returns `true` if this [`Code`] and [`Modifiers`] matches this `Accelerator`
+    /// and the modifiers pressed on each side of the keyboard satisfy [`Accelerator::sides`].
+    pub fn matches_with_sides(
+        &self,
+        modifiers: impl Borrow<Modifiers>,
+        sides: ModifierSides,
+        key: impl Borrow<Code>,
+    ) -> bool {
+        self.matches(modifiers, key)
+            && sides.left.contains(self.sides.left)
+            && sides.right.contains(self.sides.right)
+    }
+
+    // Hyper, Meta and AltGr only have to match when this accelerator uses them,
+    // since platforms may report them along with the usual modifiers.
+    fn extra_modifiers(&self) -> Modifiers {
+        EXTRA_MODIFIERS
+            .iter()
+            .map(|(modifier, _)| *modifier)
+            .filter(|modifier| self.mods.contains(*modifier))
+            .fold(Modifiers::empty(), |mods, modifier| mods | modifier)
+    }
+
+    /// Returns `true` if this logical [`Key`] and [`Modifiers`] matches this `Accelerator`.
+    ///
+    /// Shift is ignored for characters without case, like `?`, since typing them may require it.
+    /// Physical accelerators never match a [`Key`], use [`Accelerator::matches`] for them.
+    pub fn matches_key(&self, modifiers: impl Borrow<Modifiers>, key: &Key) -> bool {
+        let (Some(logical), Key::Character(character)) = (self.logical, key) else {
+            return false;
+        };
+
+        let mut chars = character.chars().flat_map(char::to_lowercase);
+        if chars.next() != Some(logical) || chars.next().is_some() {
+            return false;
+        }
+
+        let mut base_mods =
+            Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER | self.extra_modifiers();
+        if logical.is_lowercase() || self.mods.contains(Modifiers::SHIFT) {
+            base_mods |= Modifiers::SHIFT;
+        }
+        self.mods == *modifiers.borrow() & base_mods
+    }
+
+    /// Returns a human readable string of this accelerator, formatted
+    /// according to the conventions of `style`, for example `Ctrl+Shift+S`
+    /// or `⇧⌘S`. Useful for tooltips, help pages or custom-drawn UI.
+    pub fn to_display_string(&self, style: DisplayStyle) -> String {
+        let mods: &[(Modifiers, &str)] = match style {
+            DisplayStyle::Windows => &[
+                (Modifiers::CONTROL, "Ctrl+"),
+                (Modifiers::SHIFT, "Shift+"),
+                (Modifiers::ALT, "Alt+"),
+                (Modifiers::ALT_GRAPH, "AltGr+"),
+                (Modifiers::SUPER, "Windows+"),
+                (Modifiers::META, "Meta+"),
+                (Modifiers::HYPER, "Hyper+"),
+            ],
+            DisplayStyle::Gnome => &[
+                (Modifiers::SHIFT, "Shift+"),
+                (Modifiers::CONTROL, "Ctrl+"),
+                (Modifiers::ALT, "Alt+"),
+                (Modifiers::ALT_GRAPH, "AltGr+"),
+                (Modifiers::SUPER, "Super+"),
+                (Modifiers::HYPER, "Hyper+"),
+                (Modifiers::META, "Meta+"),
+            ],
+            DisplayStyle::MacOs => &[
+                (Modifiers::HYPER, "✦"),
+                (Modifiers::META, "◆"),
+                (Modifiers::CONTROL, "⌃"),
+                (Modifiers::ALT, "⌥"),
+                (Modifiers::ALT_GRAPH, "⌥"),
+                (Modifiers::SHIFT, "⇧"),
+                (Modifiers::SUPER, "⌘"),
+            ],
+        };
+
+        let mut s = String::new();
+        for (modifier, label) in mods {
+            if self.mods.contains(*modifier) {
+                s.push_str(label);
+            }
+        }
+        match (self.logical, key_label(self.key, style)) {
+            (Some(c), _) => s.extend(c.to_uppercase()),
+            (None, Some(label)) => s.push_str(label),
+            (None, None) => s.push_str(&format!("{:?}", self.key)),
+        }
+        s
+    }
+}
+
+fn normalize_modifiers(mods: Option<Modifiers>) -> Modifiers {
+    let mut mods = mods.unwrap_or_else(Modifiers::empty);
+    if mods.contains(Modifiers::META) {
+        mods.remove(Modifiers::META);
+        mods.insert(Modifiers::SUPER);
+    }
+    mods
+}
+
+/// The conventions used by [`Accelerator::to_display_string`].
+///
+/// The [`Default`] style is the one native to the current platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisplayStyle {
+    /// Windows style, for example `Ctrl+Shift+S`.
+    Windows,
+    /// GNOME style following gtk's accelerator labels, for example `Shift+Ctrl+S`.
+    Gnome,
+    /// macOS style using modifier symbols, for example `⇧⌘S`.
+    MacOs,
+}
+
+impl Default for DisplayStyle {
+    fn default() -> Self {
+        #[cfg(target_os = "macos")]
+        return DisplayStyle::MacOs;
+        #[cfg(windows)]
+        return DisplayStyle::Windows;
+        #[cfg(not(any(target_os = "macos", windows)))]
+        return DisplayStyle::Gnome;
+    }
+}
+
+fn key_label(key: Code, style: DisplayStyle) -> Option<&'static str> {
+    use Code::*;
+    use DisplayStyle::*;
+    let label = match (key, style) {
+        (KeyA, _) => "A",
+        (KeyB, _) => "B",
+        (KeyC, _) => "C",
+        (KeyD, _) => "D",
+        (KeyE, _) => "E",
+        (KeyF, _) => "F",
+        (KeyG, _) => "G",
+        (KeyH, _) => "H",
+        (KeyI, _) => "I",
+        (KeyJ, _) => "J",
+        (KeyK, _) => "K",
+        (KeyL, _) => "L",
+        (KeyM, _) => "M",
+        (KeyN, _) => "N",
+        (KeyO, _) => "O",
+        (KeyP, _) => "P",
+        (KeyQ, _) => "Q",
+        (KeyR, _) => "R",
+        (KeyS, _) => "S",
+        (KeyT, _) => "T",
+        (KeyU, _) => "U",
+        (KeyV, _) => "V",
+        (KeyW, _) => "W",
+        (KeyX, _) => "X",
+        (KeyY, _) => "Y",
+        (KeyZ, _) => "Z",
+        (Digit0, _) => "0",
+        (Digit1, _) => "1",
+        (Digit2, _) => "2",
+        (Digit3, _) => "3",
+        (Digit4, _) => "4",
+        (Digit5, _) => "5",
+        (Digit6, _) => "6",
+        (Digit7, _) => "7",
+        (Digit8, _) => "8",
+        (Digit9, _) => "9",
+        (Comma, _) => ",",
+        (Minus, _) => "-",
+        (Period, _) => ".",
+        (Equal, _) => "=",
+        (Semicolon, _) => ";",
+        (Slash, _) => "/",
+        (Backslash, Gnome) => "Backslash",
+        (Backslash, _) => "\\",
+        (Quote, _) => "'",
+        (Backquote, _) => "`",
+        (BracketLeft, _) => "[",
+        (BracketRight, _) => "]",
+        (Space, _) => "Space",
+        (Tab, MacOs) => "⇥",
+        (Tab, _) => "Tab",
+        (Enter, MacOs) => "↩",
+        (Enter, Gnome) => "Return",
+        (Enter, Windows) => "Enter",
+        (Backspace, MacOs) => "⌫",
+        (Backspace, _) => "Backspace",
+        (Escape, MacOs) => "⎋",
+        (Escape, Gnome) => "Escape",
+        (Escape, Windows) => "Esc",
+        (Delete, MacOs) => "⌦",
+        (Delete, Gnome) => "Delete",
+        (Delete, Windows) => "Del",
+        (Insert, Windows) => "Ins",
+        (Insert, _) => "Insert",
+        (Home, MacOs) => "↖",
+        (Home, _) => "Home",
+        (End, MacOs) => "↘",
+        (End, _) => "End",
+        (PageUp, MacOs) => "⇞",
+        (PageUp, Gnome) => "Page Up",
+        (PageUp, Windows) => "PgUp",
+        (PageDown, MacOs) => "⇟",
+        (PageDown, Gnome) => "Page Down",
+        (PageDown, Windows) => "PgDn",
+        // These names match LibreOffice.
+        (ArrowLeft, MacOs) => "←",
+        (ArrowLeft, _) => "Left",
+        (ArrowRight, MacOs) => "→",
+        (ArrowRight, _) => "Right",
+        (ArrowUp, MacOs) => "↑",
+        (ArrowUp, _) => "Up",
+        (ArrowDown, MacOs) => "↓",
+        (ArrowDown, _) => "Down",
+        (CapsLock, MacOs) => "⇪",
+        (CapsLock, Gnome) => "Caps Lock",
+        (CapsLock, Windows) => "CapsLock",
+        (NumLock, MacOs) => "⌧",
+        (NumLock, Gnome) => "Num Lock",
+        (NumLock, Windows) => "NumLock",
+        (ScrollLock, Gnome) => "Scroll Lock",
+        (ScrollLock, _) => "ScrLk",
+        (PrintScreen, Gnome) => "Print",
+        (PrintScreen, _) => "PrtSc",
+        (Numpad0, Gnome) => "KP 0",
+        (Numpad1, Gnome) => "KP 1",
+        (Numpad2, Gnome) => "KP 2",
+        (Numpad3, Gnome) => "KP 3",
+        (Numpad4, Gnome) => "KP 4",
+        (Numpad5, Gnome) => "KP 5",
+        (Numpad6, Gnome) => "KP 6",
+        (Numpad7, Gnome) => "KP 7",
+        (Numpad8, Gnome) => "KP 8",
+        (Numpad9, Gnome) => "KP 9",
+        (NumpadAdd, Gnome) => "KP Add",
+        (NumpadDecimal, Gnome) => "KP Decimal",
+        (NumpadDivide, Gnome) => "KP Divide",
+        (NumpadEnter, Gnome) => "KP Enter",
+        (NumpadEqual, Gnome) => "KP Equal",
+        (NumpadMultiply, Gnome) => "KP Multiply",
+        (NumpadSubtract, Gnome) => "KP Subtract",
+        (Numpad0, _) => "Num 0",
+        (Numpad1, _) => "Num 1",
+        (Numpad2, _) => "Num 2",
+        (Numpad3, _) => "Num 3",
+        (Numpad4, _) => "Num 4",
+        (Numpad5, _) => "Num 5",
+        (Numpad6, _) => "Num 6",
+        (Numpad7, _) => "Num 7",
+        (Numpad8, _) => "Num 8",
+        (Numpad9, _) => "Num 9",
+        (NumpadAdd, _) => "Num +",
+        (NumpadDecimal, _) => "Num .",
+        (NumpadDivide, _) => "Num /",
+        (NumpadEnter, MacOs) => "⌤",
+        (NumpadEnter, _) => "Num Enter",
+        (NumpadEqual, _) => "Num =",
+        (NumpadMultiply, _) => "Num *",
+        (NumpadSubtract, _) => "Num -",
+        (F1, _) => "F1",
+        (F2, _) => "F2",
+        (F3, _) => "F3",
+        (F4, _) => "F4",
+        (F5, _) => "F5",
+        (F6, _) => "F6",
+        (F7, _) => "F7",
+        (F8, _) => "F8",
+        (F9, _) => "F9",
+        (F10, _) => "F10",
+        (F11, _) => "F11",
+        (F12, _) => "F12",
+        (F13, _) => "F13",
+        (F14, _) => "F14",
+        (F15, _) => "F15",
+        (F16, _) => "F16",
+        (F17, _) => "F17",
+        (F18, _) => "F18",
+        (F19, _) => "F19",
+        (F20, _) => "F20",
+        (F21, _) => "F21",
+        (F22, _) => "F22",
+        (F23, _) => "F23",
+        (F24, _) => "F24",
+        (AudioVolumeDown, _) => "Volume Down",
+        (AudioVolumeUp, _) => "Volume Up",
+        (AudioVolumeMute, _) => "Mute",
+        _ => return None,
+    };
+    Some(label)
+}
+
+// The canonical form, for example `CmdOrCtrl+Shift+KeyS`,
+// which can be parsed back with Accelerator::from_str
+impl fmt::Display for Accelerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(target_os = "macos")]
+        let mods = [
+            (CMD_OR_CTRL, "CmdOrCtrl"),
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+        ];
+        #[cfg(not(target_os = "macos"))]
+        let mods = [
+            (CMD_OR_CTRL, "CmdOrCtrl"),
+            (Modifiers::SUPER, "Super"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+        ];
+
+        for (modifier, name) in mods {
+            if !self.mods.contains(modifier) {
+                continue;
+            }
+
+            let (left, right) = (
+                self.sides.left.contains(modifier),
+                self.sides.right.contains(modifier),
+            );
+            match SIDED_MODIFIERS.iter().find(|(m, _)| *m == modifier) {
+                Some((_, sided)) if left || right => {
+                    if left {
+                        write!(f, "Left{sided}+")?;
+                    }
+                    if right {
+                        write!(f, "Right{sided}+")?;
+                    }
+                }
+                _ => write!(f, "{name}+")?,
+            }
+        }
+        for (modifier, name) in EXTRA_MODIFIERS {
+            if self.mods.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.logical {
+            Some(c) => write!(f, "'{c}'"),
+            None => write!(f, "{}", self.key),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Accelerator {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Accelerator {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let accelerator = String::deserialize(deserializer)?;
+        parse_accelerator(&accelerator).map_err(serde::de::Error::custom)
     }
 }
 
@@ -102,7 +603,7 @@ impl Accelerator {
 impl FromStr for Accelerator {
     type Err = crate::Error;
     fn from_str(accelerator_string: &str) -> Result<Self, Self::Err> {
-        parse_accelerator(accelerator_string)
+        parse_accelerator(accelerator_string).map_err(Into::into)
     }
 }
 
@@ -110,7 +611,7 @@ impl TryFrom<&str> for Accelerator {
     type Error = crate::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        parse_accelerator(value)
+        parse_accelerator(value).map_err(Into::into)
     }
 }
 
@@ -118,190 +619,895 @@ impl TryFrom<String> for Accelerator {
     type Error = crate::Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        parse_accelerator(&value)
+        parse_accelerator(&value).map_err(Into::into)
+    }
+}
+
+/// An error returned when parsing an [`Accelerator`] or an [`AcceleratorSequence`] fails.
+///
+/// It points at the offending part of the input, for example to highlight it in a settings UI,
+/// and may suggest a known modifier or key name close to a misspelled one.
+///
+/// ```
+/// # use muda::accelerator::{AcceleratorParseErrorKind, Accelerator};
+/// let error = "Ctrk+KeyA".parse::<Accelerator>().unwrap_err();
+/// let muda::Error::AcceleratorParseError(error) = error else { unreachable!() };
+/// assert_eq!(error.kind(), AcceleratorParseErrorKind::UnknownToken);
+/// assert_eq!(error.span(), 0..4);
+/// assert_eq!(error.suggestion(), Some("Ctrl"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceleratorParseError {
+    input: String,
+    kind: AcceleratorParseErrorKind,
+    token_index: usize,
+    span: Range<usize>,
+    suggestion: Option<String>,
+}
+
+/// The reason an [`AcceleratorParseError`] happened.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AcceleratorParseErrorKind {
+    /// The input is empty or only whitespace.
+    Empty,
+    /// A token between two `+` is empty, like in `Ctrl++KeyA`.
+    EmptyToken,
+    /// A token is neither a modifier nor a key.
+    UnknownToken,
+    /// A token comes after the key, like in `Ctrl+KeyA+Shift`, or next to a quoted
+    /// logical key, like in `Ctrl+KeyA+'a'`.
+    UnexpectedToken,
+    /// There are only modifiers, like in `Ctrl+Shift`.
+    MissingKey,
+}
+
+impl AcceleratorParseError {
+    /// Returns the string that failed to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the reason parsing failed.
+    pub fn kind(&self) -> AcceleratorParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the index of the offending `+`-separated token within its accelerator.
+    ///
+    /// For [`AcceleratorParseErrorKind::MissingKey`], this is the index the key was expected at.
+    pub fn token_index(&self) -> usize {
+        self.token_index
+    }
+
+    /// Returns the byte range of the offending token in [`Self::input`].
+    ///
+    /// For [`AcceleratorParseErrorKind::MissingKey`], this is an empty range at the end of the accelerator.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the offending token.
+    pub fn token(&self) -> &str {
+        &self.input[self.span.clone()]
+    }
+
+    /// Returns a known modifier or key name close to the offending token, if any.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl fmt::Display for AcceleratorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            AcceleratorParseErrorKind::Empty => write!(f, "Empty accelerator")?,
+            AcceleratorParseErrorKind::EmptyToken => write!(f, "Unexpected empty token")?,
+            AcceleratorParseErrorKind::UnknownToken => write!(
+                f,
+                "Couldn't recognize \"{}\" as a modifier or key",
+                self.token()
+            )?,
+            AcceleratorParseErrorKind::UnexpectedToken => write!(
+                f,
+                "Unexpected \"{}\", modifiers have to come first and be followed by a single key",
+                self.token()
+            )?,
+            AcceleratorParseErrorKind::MissingKey => write!(f, "Missing key after the modifiers")?,
+        }
+        write!(
+            f,
+            " at {}..{} in \"{}\"",
+            self.span.start, self.span.end, self.input
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean \"{suggestion}\"?")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AcceleratorParseError {}
+
+/// A sequence of accelerators that have to be pressed one after the other,
+/// also known as a chord, for example `Ctrl+K Ctrl+S`.
+///
+/// Sequences are parsed from and displayed as space-separated accelerators.
+/// Use an [`AcceleratorSequenceMatcher`] to detect them from key events.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AcceleratorSequence(Vec<Accelerator>);
+
+impl AcceleratorSequence {
+    /// Creates a new sequence starting with `first`.
+    pub fn new(first: Accelerator) -> Self {
+        Self(vec![first])
+    }
+
+    /// Appends `next` to this sequence.
+    pub fn then(mut self, next: Accelerator) -> Self {
+        self.0.push(next);
+        self
+    }
+
+    /// Returns the accelerators of this sequence in the order they have to be pressed.
+    pub fn accelerators(&self) -> &[Accelerator] {
+        &self.0
+    }
+
+    /// Returns a human readable string of this sequence, see [`Accelerator::to_display_string`].
+    pub fn to_display_string(&self, style: DisplayStyle) -> String {
+        self.0
+            .iter()
+            .map(|a| a.to_display_string(style))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl From<Accelerator> for AcceleratorSequence {
+    fn from(accelerator: Accelerator) -> Self {
+        Self::new(accelerator)
+    }
+}
+
+impl fmt::Display for AcceleratorSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, accelerator) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{accelerator}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AcceleratorSequence {
+    type Err = crate::Error;
+    fn from_str(sequence_string: &str) -> Result<Self, Self::Err> {
+        parse_accelerator_sequence(sequence_string).map_err(Into::into)
+    }
+}
+
+impl TryFrom<&str> for AcceleratorSequence {
+    type Error = crate::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_accelerator_sequence(value).map_err(Into::into)
+    }
+}
+
+impl TryFrom<String> for AcceleratorSequence {
+    type Error = crate::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_accelerator_sequence(&value).map_err(Into::into)
     }
 }
 
-fn parse_accelerator(accelerator: &str) -> crate::Result<Accelerator> {
-    let tokens = accelerator.split('+').collect::<Vec<&str>>();
+#[cfg(feature = "serde")]
+impl serde::Serialize for AcceleratorSequence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AcceleratorSequence {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sequence = String::deserialize(deserializer)?;
+        parse_accelerator_sequence(&sequence).map_err(serde::de::Error::custom)
+    }
+}
+
+fn parse_accelerator_sequence(
+    sequence: &str,
+) -> Result<AcceleratorSequence, AcceleratorParseError> {
+    let accelerators = sequence
+        .split_whitespace()
+        .map(|accelerator| {
+            parse_accelerator(accelerator).map_err(|mut e| {
+                // point into the whole sequence rather than this accelerator
+                let offset = accelerator.as_ptr() as usize - sequence.as_ptr() as usize;
+                e.input = sequence.to_string();
+                e.span = e.span.start + offset..e.span.end + offset;
+                e
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if accelerators.is_empty() {
+        return Err(AcceleratorParseError {
+            input: sequence.to_string(),
+            kind: AcceleratorParseErrorKind::Empty,
+            token_index: 0,
+            span: 0..sequence.len(),
+            suggestion: None,
+        });
+    }
+
+    Ok(AcceleratorSequence(accelerators))
+}
+
+/// The result of feeding a key to an [`AcceleratorSequenceMatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SequenceMatch {
+    /// The key doesn't start any sequence.
+    NoMatch,
+    /// The key continued at least one sequence that needs more keys to complete.
+    Pending,
+    /// The sequence at this index in the matcher was completed.
+    Complete(usize),
+    /// The key didn't continue any of the pending sequences, which were abandoned.
+    ///
+    /// The key should usually be swallowed rather than handled as a regular key press.
+    Reset,
+}
+
+/// A state machine that matches key presses against a set of [`AcceleratorSequence`]s.
+///
+/// ```no_run
+/// # use muda::accelerator::{AcceleratorSequenceMatcher, Code, Modifiers, SequenceMatch};
+/// let mut matcher = AcceleratorSequenceMatcher::new(vec!["Ctrl+KeyK Ctrl+KeyS".parse().unwrap()]);
+/// assert_eq!(matcher.handle_key(Modifiers::CONTROL, Code::KeyK), SequenceMatch::Pending);
+/// assert_eq!(matcher.handle_key(Modifiers::CONTROL, Code::KeyS), SequenceMatch::Complete(0));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AcceleratorSequenceMatcher {
+    sequences: Vec<AcceleratorSequence>,
+    /// indices of the sequences matching the keys pressed so far
+    candidates: Vec<usize>,
+    position: usize,
+}
+
+impl AcceleratorSequenceMatcher {
+    /// Creates a new matcher for `sequences`.
+    pub fn new(sequences: Vec<AcceleratorSequence>) -> Self {
+        Self {
+            sequences,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the sequences of this matcher, indexed as in [`SequenceMatch::Complete`].
+    pub fn sequences(&self) -> &[AcceleratorSequence] {
+        &self.sequences
+    }
+
+    /// Adds `sequence` to this matcher and returns its index, resetting any pending sequence.
+    pub fn push(&mut self, sequence: AcceleratorSequence) -> usize {
+        self.reset();
+        self.sequences.push(sequence);
+        self.sequences.len() - 1
+    }
+
+    /// Returns `true` if some sequences have been started but not completed yet.
+    pub fn is_pending(&self) -> bool {
+        self.position > 0
+    }
+
+    /// Abandons any pending sequence.
+    pub fn reset(&mut self) {
+        self.candidates.clear();
+        self.position = 0;
+    }
+
+    /// Feeds a key press to this matcher.
+    ///
+    /// Presses of modifier keys alone are ignored and leave the state unchanged.
+    pub fn handle_key(
+        &mut self,
+        modifiers: impl Borrow<Modifiers>,
+        key: impl Borrow<Code>,
+    ) -> SequenceMatch {
+        let (modifiers, key) = (modifiers.borrow(), key.borrow());
+
+        if is_modifier_key(key) {
+            return if self.is_pending() {
+                SequenceMatch::Pending
+            } else {
+                SequenceMatch::NoMatch
+            };
+        }
+
+        let position = self.position;
+        let candidates: Vec<usize> = if self.is_pending() {
+            std::mem::take(&mut self.candidates)
+        } else {
+            (0..self.sequences.len()).collect()
+        };
+        let candidates: Vec<usize> = candidates
+            .into_iter()
+            .filter(|i| {
+                self.sequences[*i]
+                    .0
+                    .get(position)
+                    .is_some_and(|a| a.matches(modifiers, key))
+            })
+            .collect();
+
+        if let Some(complete) = candidates
+            .iter()
+            .find(|i| self.sequences[**i].0.len() == position + 1)
+        {
+            let complete = *complete;
+            self.reset();
+            SequenceMatch::Complete(complete)
+        } else if !candidates.is_empty() {
+            self.candidates = candidates;
+            self.position += 1;
+            SequenceMatch::Pending
+        } else if self.is_pending() {
+            self.reset();
+            SequenceMatch::Reset
+        } else {
+            SequenceMatch::NoMatch
+        }
+    }
+}
+
+fn is_modifier_key(key: &Code) -> bool {
+    matches!(
+        key,
+        Code::AltLeft
+            | Code::AltRight
+            | Code::ControlLeft
+            | Code::ControlRight
+            | Code::ShiftLeft
+            | Code::ShiftRight
+            | Code::MetaLeft
+            | Code::MetaRight
+            | Code::Fn
+            | Code::FnLock
+    )
+}
+
+fn parse_accelerator(accelerator: &str) -> Result<Accelerator, AcceleratorParseError> {
+    let error = |kind, token_index, span| AcceleratorParseError {
+        input: accelerator.to_string(),
+        kind,
+        token_index,
+        span,
+        suggestion: None,
+    };
+
+    if accelerator.trim().is_empty() {
+        return Err(error(
+            AcceleratorParseErrorKind::Empty,
+            0,
+            0..accelerator.len(),
+        ));
+    }
+
+    let logical = split_logical_key(accelerator);
+    let tokens = match logical {
+        Some((None, _)) => Vec::new(),
+        Some((Some(mods_end), _)) => token_spans(&accelerator[..mods_end]),
+        None => token_spans(accelerator),
+    };
 
     let mut mods = Modifiers::empty();
+    let mut extra_mods = Modifiers::empty();
+    let mut sides = ModifierSides::default();
     let mut key = None;
+    for (i, span) in tokens.iter().cloned().enumerate() {
+        let token = &accelerator[span.clone()];
 
-    match tokens.len() {
-        // single key accelerator
-        1 => {
-            key = Some(parse_key(tokens[0])?);
+        if token.is_empty() {
+            return Err(error(AcceleratorParseErrorKind::EmptyToken, i, span));
         }
-        // modifiers and key comobo accelerator
-        _ => {
-            for raw in tokens {
-                let token = raw.trim();
 
-                if token.is_empty() {
-                    return Err(crate::Error::EmptyAcceleratorToken(accelerator.to_string()));
-                }
+        if key.is_some() {
+            // At this point we have parsed the modifiers and a main key, so by reaching
+            // this code, the function either received more than one main key or
+            //  the accelerator is not in the right order
+            // examples:
+            // 1. "Ctrl+Shift+C+A" => only one main key should be allowd.
+            // 2. "Ctrl+C+Shift" => wrong order
+            return Err(error(AcceleratorParseErrorKind::UnexpectedToken, i, span));
+        }
 
-                if key.is_some() {
-                    // At this point we have parsed the modifiers and a main key, so by reaching
-                    // this code, the function either received more than one main key or
-                    //  the accelerator is not in the right order
-                    // examples:
-                    // 1. "Ctrl+Shift+C+A" => only one main key should be allowd.
-                    // 2. "Ctrl+C+Shift" => wrong order
-                    return Err(crate::Error::UnexpectedAcceleratorFormat(
-                        accelerator.to_string(),
-                    ));
-                }
+        if let Some(modifier) = parse_modifier(token) {
+            mods.insert(modifier);
+            continue;
+        }
 
-                match token.to_uppercase().as_str() {
-                    "OPTION" | "ALT" => {
-                        mods.set(Modifiers::ALT, true);
-                    }
-                    "CONTROL" | "CTRL" => {
-                        mods.set(Modifiers::CONTROL, true);
-                    }
-                    "COMMAND" | "CMD" | "SUPER" => {
-                        mods.set(Modifiers::META, true);
-                    }
-                    "SHIFT" => {
-                        mods.set(Modifiers::SHIFT, true);
-                    }
-                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
-                        #[cfg(target_os = "macos")]
-                        mods.set(Modifiers::SUPER, true);
-                        #[cfg(not(target_os = "macos"))]
-                        mods.set(Modifiers::CONTROL, true);
-                    }
-                    _ => {
-                        key = Some(parse_key(token)?);
-                    }
-                }
+        if let Some((modifier, side)) = parse_extended_modifier(token) {
+            match side {
+                Some(ModifierSide::Left) => sides.left.insert(modifier),
+                Some(ModifierSide::Right) => sides.right.insert(modifier),
+                None => extra_mods.insert(modifier),
+            }
+            continue;
+        }
+
+        match parse_key(token) {
+            // the quoted logical key has to be the only key
+            Some(_) if logical.is_some() => {
+                return Err(error(AcceleratorParseErrorKind::UnexpectedToken, i, span))
+            }
+            Some(code) => key = Some(code),
+            None => {
+                return Err(AcceleratorParseError {
+                    suggestion: suggest_name(token),
+                    ..error(AcceleratorParseErrorKind::UnknownToken, i, span)
+                })
             }
         }
     }
 
-    Ok(Accelerator::new(Some(mods), key.unwrap()))
+    let accelerator = match (logical, key) {
+        (Some((_, c)), _) => Accelerator::new_logical(Some(mods), c),
+        (None, Some(code)) => Accelerator::new(Some(mods), code),
+        (None, None) => {
+            let end = accelerator.trim_end().len();
+            return Err(error(
+                AcceleratorParseErrorKind::MissingKey,
+                tokens.len(),
+                end..end,
+            ));
+        }
+    };
+
+    Ok(accelerator.with_modifiers(extra_mods).with_sides(sides))
+}
+
+/// Splits `accelerator` on `+` and returns the trimmed byte range of every token.
+fn token_spans(accelerator: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    accelerator
+        .split('+')
+        .map(|raw| {
+            let offset = start + (raw.len() - raw.trim_start().len());
+            start += raw.len() + 1;
+            offset..offset + raw.trim().len()
+        })
+        .collect()
+}
+
+/// Splits an accelerator ending with a character in single quotes, like `Ctrl+'+'`,
+/// into the position of the `+` ending its modifiers, if it has any, and this character.
+fn split_logical_key(accelerator: &str) -> Option<(Option<usize>, char)> {
+    let quoted = accelerator.trim_end().strip_suffix('\'')?;
+    let mut chars = quoted.char_indices().rev();
+    let (_, key) = chars.next()?;
+    let (quote, '\'') = chars.next()? else {
+        return None;
+    };
+
+    let mods = quoted[..quote].trim_end();
+    if mods.trim_start().is_empty() {
+        return Some((None, key));
+    }
+    mods.strip_suffix('+').map(|mods| (Some(mods.len()), key))
+}
+
+fn parse_modifier(token: &str) -> Option<Modifiers> {
+    match token.to_uppercase().as_str() {
+        "OPTION" | "ALT" => Some(Modifiers::ALT),
+        "CONTROL" | "CTRL" => Some(Modifiers::CONTROL),
+        "COMMAND" | "CMD" | "SUPER" => Some(Modifiers::SUPER),
+        "SHIFT" => Some(Modifiers::SHIFT),
+        "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => Some(CMD_OR_CTRL),
+        _ => None,
+    }
+}
+
+enum ModifierSide {
+    Left,
+    Right,
 }
 
-fn parse_key(key: &str) -> crate::Result<Code> {
+/// Parses the modifiers added by [`Accelerator::with_modifiers`] and [`Accelerator::with_sides`].
+fn parse_extended_modifier(token: &str) -> Option<(Modifiers, Option<ModifierSide>)> {
+    let token = token.to_uppercase();
+    let (side, name) = if let Some(name) = token.strip_prefix("LEFT") {
+        (Some(ModifierSide::Left), name)
+    } else if let Some(name) = token.strip_prefix("RIGHT") {
+        (Some(ModifierSide::Right), name)
+    } else {
+        (None, token.as_str())
+    };
+
+    let modifier = match (&side, name) {
+        (None, "HYPER") => Modifiers::HYPER,
+        (None, "META") => Modifiers::META,
+        (None, "ALTGR" | "ALTGRAPH") => Modifiers::ALT_GRAPH,
+        (Some(_), "OPTION" | "ALT") => Modifiers::ALT,
+        (Some(_), "CONTROL" | "CTRL") => Modifiers::CONTROL,
+        (Some(_), "COMMAND" | "CMD" | "SUPER") => Modifiers::SUPER,
+        (Some(_), "SHIFT") => Modifiers::SHIFT,
+        _ => return None,
+    };
+    Some((modifier, side))
+}
+
+fn parse_key(key: &str) -> Option<Code> {
     use Code::*;
     match key.to_uppercase().as_str() {
-        "BACKQUOTE" | "`" => Ok(Backquote),
-        "BACKSLASH" | "\\" => Ok(Backslash),
-        "BRACKETLEFT" | "[" => Ok(BracketLeft),
-        "BRACKETRIGHT" | "]" => Ok(BracketRight),
-        "COMMA" | "," => Ok(Comma),
-        "DIGIT0" | "0" => Ok(Digit0),
-        "DIGIT1" | "1" => Ok(Digit1),
-        "DIGIT2" | "2" => Ok(Digit2),
-        "DIGIT3" | "3" => Ok(Digit3),
-        "DIGIT4" | "4" => Ok(Digit4),
-        "DIGIT5" | "5" => Ok(Digit5),
-        "DIGIT6" | "6" => Ok(Digit6),
-        "DIGIT7" | "7" => Ok(Digit7),
-        "DIGIT8" | "8" => Ok(Digit8),
-        "DIGIT9" | "9" => Ok(Digit9),
-        "EQUAL" | "=" => Ok(Equal),
-        "KEYA" | "A" => Ok(KeyA),
-        "KEYB" | "B" => Ok(KeyB),
-        "KEYC" | "C" => Ok(KeyC),
-        "KEYD" | "D" => Ok(KeyD),
-        "KEYE" | "E" => Ok(KeyE),
-        "KEYF" | "F" => Ok(KeyF),
-        "KEYG" | "G" => Ok(KeyG),
-        "KEYH" | "H" => Ok(KeyH),
-        "KEYI" | "I" => Ok(KeyI),
-        "KEYJ" | "J" => Ok(KeyJ),
-        "KEYK" | "K" => Ok(KeyK),
-        "KEYL" | "L" => Ok(KeyL),
-        "KEYM" | "M" => Ok(KeyM),
-        "KEYN" | "N" => Ok(KeyN),
-        "KEYO" | "O" => Ok(KeyO),
-        "KEYP" | "P" => Ok(KeyP),
-        "KEYQ" | "Q" => Ok(KeyQ),
-        "KEYR" | "R" => Ok(KeyR),
-        "KEYS" | "S" => Ok(KeyS),
-        "KEYT" | "T" => Ok(KeyT),
-        "KEYU" | "U" => Ok(KeyU),
-        "KEYV" | "V" => Ok(KeyV),
-        "KEYW" | "W" => Ok(KeyW),
-        "KEYX" | "X" => Ok(KeyX),
-        "KEYY" | "Y" => Ok(KeyY),
-        "KEYZ" | "Z" => Ok(KeyZ),
-        "MINUS" | "-" => Ok(Minus),
-        "PERIOD" | "." => Ok(Period),
-        "QUOTE" | "'" => Ok(Quote),
-        "SEMICOLON" | ";" => Ok(Semicolon),
-        "SLASH" | "/" => Ok(Slash),
-        "BACKSPACE" => Ok(Backspace),
-        "CAPSLOCK" => Ok(CapsLock),
-        "ENTER" => Ok(Enter),
-        "SPACE" => Ok(Space),
-        "TAB" => Ok(Tab),
-        "DELETE" => Ok(Delete),
-        "END" => Ok(End),
-        "HOME" => Ok(Home),
-        "INSERT" => Ok(Insert),
-        "PAGEDOWN" => Ok(PageDown),
-        "PAGEUP" => Ok(PageUp),
-        "PRINTSCREEN" => Ok(PrintScreen),
-        "SCROLLLOCK" => Ok(ScrollLock),
-        "ARROWDOWN" | "DOWN" => Ok(ArrowDown),
-        "ARROWLEFT" | "LEFT" => Ok(ArrowLeft),
-        "ARROWRIGHT" | "RIGHT" => Ok(ArrowRight),
-        "ARROWUP" | "UP" => Ok(ArrowUp),
-        "NUMLOCK" => Ok(NumLock),
-        "NUMPAD0" | "NUM0" => Ok(Numpad0),
-        "NUMPAD1" | "NUM1" => Ok(Numpad1),
-        "NUMPAD2" | "NUM2" => Ok(Numpad2),
-        "NUMPAD3" | "NUM3" => Ok(Numpad3),
-        "NUMPAD4" | "NUM4" => Ok(Numpad4),
-        "NUMPAD5" | "NUM5" => Ok(Numpad5),
-        "NUMPAD6" | "NUM6" => Ok(Numpad6),
-        "NUMPAD7" | "NUM7" => Ok(Numpad7),
-        "NUMPAD8" | "NUM8" => Ok(Numpad8),
-        "NUMPAD9" | "NUM9" => Ok(Numpad9),
-        "NUMPADADD" | "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => Ok(NumpadAdd),
-        "NUMPADDECIMAL" | "NUMDECIMAL" => Ok(NumpadDecimal),
-        "NUMPADDIVIDE" | "NUMDIVIDE" => Ok(NumpadDivide),
-        "NUMPADENTER" | "NUMENTER" => Ok(NumpadEnter),
-        "NUMPADEQUAL" | "NUMEQUAL" => Ok(NumpadEqual),
-        "NUMPADMULTIPLY" | "NUMMULTIPLY" => Ok(NumpadMultiply),
-        "NUMPADSUBTRACT" | "NUMSUBTRACT" => Ok(NumpadSubtract),
-        "ESCAPE" | "ESC" => Ok(Escape),
-        "F1" => Ok(F1),
-        "F2" => Ok(F2),
-        "F3" => Ok(F3),
-        "F4" => Ok(F4),
-        "F5" => Ok(F5),
-        "F6" => Ok(F6),
-        "F7" => Ok(F7),
-        "F8" => Ok(F8),
-        "F9" => Ok(F9),
-        "F10" => Ok(F10),
-        "F11" => Ok(F11),
-        "F12" => Ok(F12),
-        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => Ok(AudioVolumeDown),
-        "AUDIOVOLUMEUP" | "VOLUMEUP" => Ok(AudioVolumeUp),
-        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => Ok(AudioVolumeMute),
-        "F13" => Ok(F13),
-        "F14" => Ok(F14),
-        "F15" => Ok(F15),
-        "F16" => Ok(F16),
-        "F17" => Ok(F17),
-        "F18" => Ok(F18),
-        "F19" => Ok(F19),
-        "F20" => Ok(F20),
-        "F21" => Ok(F21),
-        "F22" => Ok(F22),
-        "F23" => Ok(F23),
-        "F24" => Ok(F24),
-
-        _ => Err(crate::Error::UnrecognizedAcceleratorCode(key.to_string())),
+        "BACKQUOTE" | "`" => Some(Backquote),
+        "BACKSLASH" | "\\" => Some(Backslash),
+        "BRACKETLEFT" | "[" => Some(BracketLeft),
+        "BRACKETRIGHT" | "]" => Some(BracketRight),
+        "COMMA" | "," => Some(Comma),
+        "DIGIT0" | "0" => Some(Digit0),
+        "DIGIT1" | "1" => Some(Digit1),
+        "DIGIT2" | "2" => Some(Digit2),
+        "DIGIT3" | "3" => Some(Digit3),
+        "DIGIT4" | "4" => Some(Digit4),
+        "DIGIT5" | "5" => Some(Digit5),
+        "DIGIT6" | "6" => Some(Digit6),
+        "DIGIT7" | "7" => Some(Digit7),
+        "DIGIT8" | "8" => Some(Digit8),
+        "DIGIT9" | "9" => Some(Digit9),
+        "EQUAL" | "=" => Some(Equal),
+        "KEYA" | "A" => Some(KeyA),
+        "KEYB" | "B" => Some(KeyB),
+        "KEYC" | "C" => Some(KeyC),
+        "KEYD" | "D" => Some(KeyD),
+        "KEYE" | "E" => Some(KeyE),
+        "KEYF" | "F" => Some(KeyF),
+        "KEYG" | "G" => Some(KeyG),
+        "KEYH" | "H" => Some(KeyH),
+        "KEYI" | "I" => Some(KeyI),
+        "KEYJ" | "J" => Some(KeyJ),
+        "KEYK" | "K" => Some(KeyK),
+        "KEYL" | "L" => Some(KeyL),
+        "KEYM" | "M" => Some(KeyM),
+        "KEYN" | "N" => Some(KeyN),
+        "KEYO" | "O" => Some(KeyO),
+        "KEYP" | "P" => Some(KeyP),
+        "KEYQ" | "Q" => Some(KeyQ),
+        "KEYR" | "R" => Some(KeyR),
+        "KEYS" | "S" => Some(KeyS),
+        "KEYT" | "T" => Some(KeyT),
+        "KEYU" | "U" => Some(KeyU),
+        "KEYV" | "V" => Some(KeyV),
+        "KEYW" | "W" => Some(KeyW),
+        "KEYX" | "X" => Some(KeyX),
+        "KEYY" | "Y" => Some(KeyY),
+        "KEYZ" | "Z" => Some(KeyZ),
+        "MINUS" | "-" => Some(Minus),
+        "PERIOD" | "." => Some(Period),
+        "QUOTE" | "'" => Some(Quote),
+        "SEMICOLON" | ";" => Some(Semicolon),
+        "SLASH" | "/" => Some(Slash),
+        "BACKSPACE" => Some(Backspace),
+        "CAPSLOCK" => Some(CapsLock),
+        "ENTER" => Some(Enter),
+        "SPACE" => Some(Space),
+        "TAB" => Some(Tab),
+        "DELETE" => Some(Delete),
+        "END" => Some(End),
+        "HOME" => Some(Home),
+        "INSERT" => Some(Insert),
+        "PAGEDOWN" => Some(PageDown),
+        "PAGEUP" => Some(PageUp),
+        "PRINTSCREEN" => Some(PrintScreen),
+        "SCROLLLOCK" => Some(ScrollLock),
+        "ARROWDOWN" | "DOWN" => Some(ArrowDown),
+        "ARROWLEFT" | "LEFT" => Some(ArrowLeft),
+        "ARROWRIGHT" | "RIGHT" => Some(ArrowRight),
+        "ARROWUP" | "UP" => Some(ArrowUp),
+        "NUMLOCK" => Some(NumLock),
+        "NUMPAD0" | "NUM0" => Some(Numpad0),
+        "NUMPAD1" | "NUM1" => Some(Numpad1),
+        "NUMPAD2" | "NUM2" => Some(Numpad2),
+        "NUMPAD3" | "NUM3" => Some(Numpad3),
+        "NUMPAD4" | "NUM4" => Some(Numpad4),
+        "NUMPAD5" | "NUM5" => Some(Numpad5),
+        "NUMPAD6" | "NUM6" => Some(Numpad6),
+        "NUMPAD7" | "NUM7" => Some(Numpad7),
+        "NUMPAD8" | "NUM8" => Some(Numpad8),
+        "NUMPAD9" | "NUM9" => Some(Numpad9),
+        "NUMPADADD" | "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => Some(NumpadAdd),
+        "NUMPADDECIMAL" | "NUMDECIMAL" => Some(NumpadDecimal),
+        "NUMPADDIVIDE" | "NUMDIVIDE" => Some(NumpadDivide),
+        "NUMPADENTER" | "NUMENTER" => Some(NumpadEnter),
+        "NUMPADEQUAL" | "NUMEQUAL" => Some(NumpadEqual),
+        "NUMPADMULTIPLY" | "NUMMULTIPLY" => Some(NumpadMultiply),
+        "NUMPADSUBTRACT" | "NUMSUBTRACT" => Some(NumpadSubtract),
+        "ESCAPE" | "ESC" => Some(Escape),
+        "F1" => Some(F1),
+        "F2" => Some(F2),
+        "F3" => Some(F3),
+        "F4" => Some(F4),
+        "F5" => Some(F5),
+        "F6" => Some(F6),
+        "F7" => Some(F7),
+        "F8" => Some(F8),
+        "F9" => Some(F9),
+        "F10" => Some(F10),
+        "F11" => Some(F11),
+        "F12" => Some(F12),
+        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => Some(AudioVolumeDown),
+        "AUDIOVOLUMEUP" | "VOLUMEUP" => Some(AudioVolumeUp),
+        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => Some(AudioVolumeMute),
+        "F13" => Some(F13),
+        "F14" => Some(F14),
+        "F15" => Some(F15),
+        "F16" => Some(F16),
+        "F17" => Some(F17),
+        "F18" => Some(F18),
+        "F19" => Some(F19),
+        "F20" => Some(F20),
+        "F21" => Some(F21),
+        "F22" => Some(F22),
+        "F23" => Some(F23),
+        "F24" => Some(F24),
+        "PAUSE" | "BREAK" => Some(Pause),
+        "CONTEXTMENU" | "MENU" | "APPS" => Some(ContextMenu),
+        "HELP" => Some(Help),
+        "NUMPADCOMMA" | "NUMCOMMA" => Some(NumpadComma),
+        "MEDIAPLAYPAUSE" | "PLAYPAUSE" => Some(MediaPlayPause),
+        "MEDIASTOP" => Some(MediaStop),
+        "MEDIATRACKNEXT" | "NEXTTRACK" => Some(MediaTrackNext),
+        "MEDIATRACKPREVIOUS" | "PREVTRACK" | "PREVIOUSTRACK" => Some(MediaTrackPrevious),
+        "MEDIASELECT" => Some(MediaSelect),
+        "BROWSERBACK" => Some(BrowserBack),
+        "BROWSERFORWARD" => Some(BrowserForward),
+        "BROWSERREFRESH" => Some(BrowserRefresh),
+        "BROWSERSTOP" => Some(BrowserStop),
+        "BROWSERSEARCH" => Some(BrowserSearch),
+        "BROWSERFAVORITES" => Some(BrowserFavorites),
+        "BROWSERHOME" => Some(BrowserHome),
+        "LAUNCHMAIL" => Some(LaunchMail),
+        "LAUNCHAPP1" => Some(LaunchApp1),
+        "LAUNCHAPP2" => Some(LaunchApp2),
+        "INTLBACKSLASH" => Some(IntlBackslash),
+        "INTLRO" => Some(IntlRo),
+        "INTLYEN" => Some(IntlYen),
+        "CONVERT" => Some(Convert),
+        "NONCONVERT" => Some(NonConvert),
+        "KANAMODE" => Some(KanaMode),
+        "LANG1" => Some(Lang1),
+        "LANG2" => Some(Lang2),
+        "LANG3" => Some(Lang3),
+        "LANG4" => Some(Lang4),
+        "LANG5" => Some(Lang5),
+
+        // any other supported `Code` by its name, as written by `Accelerator::to_string`,
+        // other codes like `ShiftLeft` or `Fn` aren't mapped by the backends
+        _ => Code::from_str(key)
+            .ok()
+            .filter(|code| PARSEABLE_CODES.contains(code)),
     }
 }
 
+/// Every [`Code`] that [`parse_key`] recognizes by name, used for suggestions.
+/// The backends are tested to map each of them.
+pub(crate) const PARSEABLE_CODES: &[Code] = {
+    use Code::*;
+    &[
+        Backquote,
+        Backslash,
+        BracketLeft,
+        BracketRight,
+        Comma,
+        Equal,
+        Minus,
+        Period,
+        Quote,
+        Semicolon,
+        Slash,
+        Digit0,
+        Digit1,
+        Digit2,
+        Digit3,
+        Digit4,
+        Digit5,
+        Digit6,
+        Digit7,
+        Digit8,
+        Digit9,
+        KeyA,
+        KeyB,
+        KeyC,
+        KeyD,
+        KeyE,
+        KeyF,
+        KeyG,
+        KeyH,
+        KeyI,
+        KeyJ,
+        KeyK,
+        KeyL,
+        KeyM,
+        KeyN,
+        KeyO,
+        KeyP,
+        KeyQ,
+        KeyR,
+        KeyS,
+        KeyT,
+        KeyU,
+        KeyV,
+        KeyW,
+        KeyX,
+        KeyY,
+        KeyZ,
+        Backspace,
+        CapsLock,
+        Enter,
+        Space,
+        Tab,
+        Delete,
+        End,
+        Home,
+        Insert,
+        PageDown,
+        PageUp,
+        PrintScreen,
+        ScrollLock,
+        ArrowDown,
+        ArrowLeft,
+        ArrowRight,
+        ArrowUp,
+        NumLock,
+        Numpad0,
+        Numpad1,
+        Numpad2,
+        Numpad3,
+        Numpad4,
+        Numpad5,
+        Numpad6,
+        Numpad7,
+        Numpad8,
+        Numpad9,
+        NumpadAdd,
+        NumpadDecimal,
+        NumpadDivide,
+        NumpadEnter,
+        NumpadEqual,
+        NumpadMultiply,
+        NumpadSubtract,
+        Escape,
+        F1,
+        F2,
+        F3,
+        F4,
+        F5,
+        F6,
+        F7,
+        F8,
+        F9,
+        F10,
+        F11,
+        F12,
+        AudioVolumeDown,
+        AudioVolumeUp,
+        AudioVolumeMute,
+        F13,
+        F14,
+        F15,
+        F16,
+        F17,
+        F18,
+        F19,
+        F20,
+        F21,
+        F22,
+        F23,
+        F24,
+        Pause,
+        ContextMenu,
+        Help,
+        NumpadComma,
+        MediaPlayPause,
+        MediaStop,
+        MediaTrackNext,
+        MediaTrackPrevious,
+        MediaSelect,
+        BrowserBack,
+        BrowserForward,
+        BrowserRefresh,
+        BrowserStop,
+        BrowserSearch,
+        BrowserFavorites,
+        BrowserHome,
+        LaunchMail,
+        LaunchApp1,
+        LaunchApp2,
+        IntlBackslash,
+        IntlRo,
+        IntlYen,
+        Convert,
+        NonConvert,
+        KanaMode,
+        Lang1,
+        Lang2,
+        Lang3,
+        Lang4,
+        Lang5,
+    ]
+};
+
+/// Returns the modifier or key name closest to the unknown `token`, if any is close enough.
+fn suggest_name(token: &str) -> Option<String> {
+    const MODIFIERS: &[&str] = &[
+        "Alt",
+        "Option",
+        "Ctrl",
+        "Control",
+        "Shift",
+        "Super",
+        "Cmd",
+        "Command",
+        "CmdOrCtrl",
+        "CommandOrControl",
+        "Hyper",
+        "Meta",
+        "AltGr",
+    ];
+
+    let max_distance = (token.chars().count() / 3).max(1);
+    MODIFIERS
+        .iter()
+        .map(|name| name.to_string())
+        .chain(PARSEABLE_CODES.iter().map(Code::to_string))
+        .map(|name| (edit_distance(token, &name), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Case-insensitive Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase().chars().collect::<Vec<_>>();
+    let b = b.to_lowercase().chars().collect::<Vec<_>>();
+
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[test]
+fn test_parse_key() {
+    for code in PARSEABLE_CODES {
+        let name = code.to_string();
+        assert_eq!(parse_key(&name).unwrap(), *code);
+        assert_eq!(parse_key(&name.to_lowercase()).unwrap(), *code);
+    }
+
+    assert_eq!(parse_key("PlayPause").unwrap(), Code::MediaPlayPause);
+    assert_eq!(parse_key("PrevTrack").unwrap(), Code::MediaTrackPrevious);
+    assert_eq!(parse_key("Menu").unwrap(), Code::ContextMenu);
+    assert_eq!(parse_key("Break").unwrap(), Code::Pause);
+    assert!(parse_key("Unidentified").is_none());
+    assert!(parse_key("ShiftLeft").is_none());
+    assert!(parse_key("Fn").is_none());
+    assert!(parse_key("Power").is_none());
+    assert!("Ctrl+ShiftLeft".parse::<Accelerator>().is_err());
+    assert!("Ctrl+Fn".parse::<Accelerator>().is_err());
+}
+
 #[test]
 fn test_parse_accelerator() {
     macro_rules! assert_parse_accelerator {
@@ -318,6 +1524,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::empty(),
             key: Code::KeyX,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -327,6 +1535,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::CONTROL,
             key: Code::KeyX,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -336,6 +1546,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SHIFT,
             key: Code::KeyC,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -345,6 +1557,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SHIFT,
             key: Code::KeyC,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -354,6 +1568,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SUPER | Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::ALT,
             key: Code::ArrowUp,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -362,6 +1578,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::empty(),
             key: Code::Digit5,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -370,6 +1588,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::empty(),
             key: Code::KeyG,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -379,6 +1599,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SHIFT,
             key: Code::F12,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -391,6 +1613,8 @@ fn test_parse_accelerator() {
             #[cfg(not(target_os = "macos"))]
             mods: Modifiers::CONTROL,
             key: Code::Space,
+            logical: None,
+            sides: ModifierSides::default(),
             id: 0,
         }
     );
@@ -414,3 +1638,406 @@ fn test_equality() {
             && h5.id() != h6.id()
     );
 }
+
+#[test]
+fn test_display_string() {
+    let a = parse_accelerator("Ctrl+Shift+KeyS").unwrap();
+    assert_eq!(a.to_display_string(DisplayStyle::Windows), "Ctrl+Shift+S");
+    assert_eq!(a.to_display_string(DisplayStyle::Gnome), "Shift+Ctrl+S");
+    assert_eq!(a.to_display_string(DisplayStyle::MacOs), "⌃⇧S");
+
+    let a = parse_accelerator("Super+Alt+PageUp").unwrap();
+    assert_eq!(
+        a.to_display_string(DisplayStyle::Windows),
+        "Alt+Windows+PgUp"
+    );
+    assert_eq!(
+        a.to_display_string(DisplayStyle::Gnome),
+        "Alt+Super+Page Up"
+    );
+    assert_eq!(a.to_display_string(DisplayStyle::MacOs), "⌥⌘⇞");
+
+    let a = parse_accelerator("Backslash").unwrap();
+    assert_eq!(a.to_display_string(DisplayStyle::Gnome), "Backslash");
+    assert_eq!(a.to_display_string(DisplayStyle::MacOs), "\\");
+}
+
+#[test]
+fn test_to_string_round_trip() {
+    for accelerator in [
+        Accelerator::new(Some(CMD_OR_CTRL | Modifiers::SHIFT), Code::KeyS),
+        Accelerator::new(
+            Some(Modifiers::SUPER | Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT),
+            Code::ArrowUp,
+        ),
+        Accelerator::new(None, Code::F24),
+        Accelerator::new(Some(Modifiers::ALT), Code::MediaPlayPause),
+        Accelerator::new_logical(Some(CMD_OR_CTRL), 'z'),
+        Accelerator::new_logical(Some(Modifiers::CONTROL), '+'),
+        Accelerator::new_logical(None, '\''),
+    ] {
+        assert_eq!(
+            accelerator.to_string().parse::<Accelerator>().unwrap(),
+            accelerator
+        );
+    }
+
+    assert_eq!(
+        Accelerator::new(Some(CMD_OR_CTRL | Modifiers::SHIFT), Code::KeyS).to_string(),
+        "CmdOrCtrl+Shift+KeyS"
+    );
+}
+
+#[test]
+fn test_logical_accelerator() {
+    let accelerator: Accelerator = "Alt+Shift+'Z'".parse().unwrap();
+    assert_eq!(
+        accelerator,
+        Accelerator::new_logical(Some(Modifiers::ALT | Modifiers::SHIFT), 'z')
+    );
+    assert_eq!(accelerator.logical_key(), Some('z'));
+    assert_ne!(
+        accelerator,
+        Accelerator::new(Some(Modifiers::ALT | Modifiers::SHIFT), Code::KeyZ)
+    );
+    assert_eq!(accelerator.to_string(), "Alt+Shift+'z'");
+    assert_eq!(
+        accelerator.to_display_string(DisplayStyle::Windows),
+        "Shift+Alt+Z"
+    );
+
+    assert_eq!(
+        parse_accelerator("'+'").unwrap(),
+        Accelerator::new_logical(None, '+')
+    );
+    assert_eq!(
+        parse_accelerator("Ctrl + '/'").unwrap(),
+        Accelerator::new_logical(Some(Modifiers::CONTROL), '/')
+    );
+    // a lone quote is still the physical key
+    assert_eq!(
+        parse_accelerator("Ctrl+'").unwrap(),
+        Accelerator::new(Some(Modifiers::CONTROL), Code::Quote)
+    );
+    assert!(parse_accelerator("Ctrl+KeyA+'a'").is_err());
+    assert!(parse_accelerator("Ctrl++'a'").is_err());
+    assert!(parse_accelerator("Ctrl'a'").is_err());
+
+    let key = Key::Character("Z".into());
+    assert!(accelerator.matches_key(Modifiers::ALT | Modifiers::SHIFT, &key));
+    assert!(!accelerator.matches_key(Modifiers::ALT, &key));
+    assert!(!accelerator.matches(Modifiers::ALT | Modifiers::SHIFT, Code::KeyZ));
+
+    let question = Accelerator::new_logical(Some(Modifiers::CONTROL), '?');
+    let key = Key::Character("?".into());
+    assert!(question.matches_key(Modifiers::CONTROL | Modifiers::SHIFT, &key));
+    assert!(question.matches_key(Modifiers::CONTROL, &key));
+    assert!(!question.matches_key(Modifiers::ALT, &key));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize() {
+    use serde::{de::IntoDeserializer, Deserialize};
+
+    let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+        "Alt+Shift+KeyQ".into_deserializer();
+    assert_eq!(
+        Accelerator::deserialize(deserializer).unwrap(),
+        Accelerator::new(Some(Modifiers::ALT | Modifiers::SHIFT), Code::KeyQ)
+    );
+}
+
+#[test]
+fn test_parse_accelerator_sequence() {
+    let sequence: AcceleratorSequence = "Ctrl+KeyK  Ctrl+KeyS".parse().unwrap();
+    assert_eq!(
+        sequence,
+        AcceleratorSequence::new(Accelerator::new(Some(Modifiers::CONTROL), Code::KeyK))
+            .then(Accelerator::new(Some(Modifiers::CONTROL), Code::KeyS))
+    );
+    assert_eq!(
+        sequence.to_string().parse::<AcceleratorSequence>().unwrap(),
+        sequence
+    );
+    assert_eq!(
+        sequence.to_display_string(DisplayStyle::Windows),
+        "Ctrl+K Ctrl+S"
+    );
+    assert!("".parse::<AcceleratorSequence>().is_err());
+    assert!("Ctrl+KeyK Ctrl+".parse::<AcceleratorSequence>().is_err());
+}
+
+#[test]
+fn test_accelerator_sequence_matcher() {
+    let mut matcher = AcceleratorSequenceMatcher::new(vec![
+        "Ctrl+KeyK Ctrl+KeyS".parse().unwrap(),
+        "Ctrl+KeyK KeyC".parse().unwrap(),
+        "F5".parse().unwrap(),
+    ]);
+
+    assert_eq!(
+        matcher.handle_key(Modifiers::empty(), Code::F5),
+        SequenceMatch::Complete(2)
+    );
+    assert_eq!(
+        matcher.handle_key(Modifiers::empty(), Code::KeyK),
+        SequenceMatch::NoMatch
+    );
+
+    assert_eq!(
+        matcher.handle_key(Modifiers::CONTROL, Code::KeyK),
+        SequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.handle_key(Modifiers::CONTROL, Code::ControlLeft),
+        SequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.handle_key(Modifiers::empty(), Code::KeyC),
+        SequenceMatch::Complete(1)
+    );
+    assert!(!matcher.is_pending());
+
+    assert_eq!(
+        matcher.handle_key(Modifiers::CONTROL, Code::KeyK),
+        SequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.handle_key(Modifiers::empty(), Code::F5),
+        SequenceMatch::Reset
+    );
+    assert_eq!(
+        matcher.handle_key(Modifiers::empty(), Code::F5),
+        SequenceMatch::Complete(2)
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = parse_accelerator("Ctrk+Shift+KeyA").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::UnknownToken);
+    assert_eq!(error.token_index(), 0);
+    assert_eq!(error.span(), 0..4);
+    assert_eq!(error.token(), "Ctrk");
+    assert_eq!(error.suggestion(), Some("Ctrl"));
+    assert_eq!(
+        error.to_string(),
+        "Couldn't recognize \"Ctrk\" as a modifier or key at 0..4 in \"Ctrk+Shift+KeyA\", did you mean \"Ctrl\"?"
+    );
+
+    let error = parse_accelerator("Shift + PgeUp").unwrap_err();
+    assert_eq!(error.token_index(), 1);
+    assert_eq!(error.span(), 8..13);
+    assert_eq!(error.suggestion(), Some("PageUp"));
+
+    let error = parse_accelerator("Ctrl+Shift").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::MissingKey);
+    assert_eq!(error.token_index(), 2);
+    assert_eq!(error.span(), 10..10);
+
+    let error = parse_accelerator("Ctrl++KeyA").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::EmptyToken);
+    assert_eq!(error.token_index(), 1);
+    assert_eq!(error.span(), 5..5);
+
+    let error = parse_accelerator("Ctrl+KeyC+Shift").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::UnexpectedToken);
+    assert_eq!(error.token_index(), 2);
+    assert_eq!(error.token(), "Shift");
+
+    let error = parse_accelerator("Ctrl+KeyA+'a'").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::UnexpectedToken);
+    assert_eq!(error.token(), "KeyA");
+
+    let error = parse_accelerator("  ").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::Empty);
+
+    let error = parse_accelerator("Ctrl+@@@@").unwrap_err();
+    assert_eq!(error.suggestion(), None);
+
+    let error = parse_accelerator_sequence("Ctrl+KeyK  Ctrl+Kye").unwrap_err();
+    assert_eq!(error.input(), "Ctrl+KeyK  Ctrl+Kye");
+    assert_eq!(error.token_index(), 1);
+    assert_eq!(error.span(), 16..19);
+    assert_eq!(error.token(), "Kye");
+    assert_eq!(error.suggestion(), Some("KeyE"));
+
+    let error = parse_accelerator_sequence("").unwrap_err();
+    assert_eq!(error.kind(), AcceleratorParseErrorKind::Empty);
+
+    assert!(matches!(
+        "Ctrl+Shift".parse::<Accelerator>(),
+        Err(crate::Error::AcceleratorParseError(_))
+    ));
+}
+
+#[test]
+fn test_parse_arbitrary_input() {
+    const PIECES: &[&str] = &[
+        "+",
+        "'",
+        " ",
+        "\t",
+        "Ctrl",
+        "shift",
+        "Alt",
+        "CmdOrCtrl",
+        "Super",
+        "KeyA",
+        "q",
+        "F13",
+        "PageUp",
+        "Numpad5",
+        "Unidentified",
+        "é",
+        "ß",
+        "日本",
+        "🦀",
+        "\u{3000}",
+        "''",
+        "++",
+        "Hyper",
+        "Meta",
+        "AltGr",
+        "LeftCtrl",
+        "RightShift",
+    ];
+
+    // a small xorshift generator, so failures are reproducible
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..5_000 {
+        let len = next() % 8;
+        let input = (0..len)
+            .map(|_| PIECES[(next() % PIECES.len() as u64) as usize])
+            .collect::<String>();
+
+        match parse_accelerator(&input) {
+            Ok(accelerator) => {
+                assert_eq!(
+                    parse_accelerator(&accelerator.to_string()),
+                    Ok(accelerator),
+                    "{input:?}"
+                );
+            }
+            Err(error) => {
+                assert_eq!(error.input(), input);
+                assert!(input.get(error.span()).is_some(), "{input:?}");
+                let _ = error.to_string();
+            }
+        }
+
+        match parse_accelerator_sequence(&input) {
+            Ok(sequence) => {
+                assert_eq!(
+                    parse_accelerator_sequence(&sequence.to_string()),
+                    Ok(sequence),
+                    "{input:?}"
+                );
+            }
+            Err(error) => {
+                assert!(input.get(error.span()).is_some(), "{input:?}");
+                let _ = error.to_string();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_extended_modifiers() {
+    let accelerator = parse_accelerator("Hyper+Shift+KeyK").unwrap();
+    assert_eq!(
+        accelerator,
+        Accelerator::new(Some(Modifiers::SHIFT), Code::KeyK).with_modifiers(Modifiers::HYPER)
+    );
+    assert_eq!(accelerator.to_string(), "Shift+Hyper+KeyK");
+    assert!(accelerator.matches(Modifiers::HYPER | Modifiers::SHIFT, Code::KeyK));
+    assert!(!accelerator.matches(Modifiers::SHIFT, Code::KeyK));
+
+    // Meta is not folded into Super
+    let accelerator = parse_accelerator("Meta+KeyM").unwrap();
+    assert_eq!(accelerator.mods, Modifiers::META);
+    assert_ne!(accelerator, parse_accelerator("Super+KeyM").unwrap());
+
+    // extra modifiers are ignored by accelerators that don't use them
+    let accelerator = parse_accelerator("Alt+KeyA").unwrap();
+    assert!(accelerator.matches(Modifiers::ALT | Modifiers::META, Code::KeyA));
+
+    for input in ["Alt+Hyper", "Alt+Meta"] {
+        assert_eq!(
+            parse_accelerator(input).unwrap_err().kind(),
+            AcceleratorParseErrorKind::MissingKey
+        );
+    }
+
+    let accelerator = parse_accelerator("AltGr+KeyE").unwrap();
+    assert_eq!(accelerator.mods, Modifiers::ALT_GRAPH);
+    assert_eq!(accelerator.to_string(), "AltGr+KeyE");
+    assert_eq!(
+        accelerator.to_display_string(DisplayStyle::Windows),
+        "AltGr+E"
+    );
+    assert_eq!(accelerator.to_display_string(DisplayStyle::MacOs), "⌥E");
+
+    let accelerator = Accelerator::new(Some(Modifiers::SHIFT), Code::KeyK)
+        .with_modifiers(Modifiers::CONTROL | Modifiers::HYPER | Modifiers::META);
+    assert_eq!(
+        accelerator.to_display_string(DisplayStyle::Windows),
+        "Ctrl+Shift+Meta+Hyper+K"
+    );
+    assert_eq!(
+        accelerator.to_display_string(DisplayStyle::Gnome),
+        "Shift+Ctrl+Hyper+Meta+K"
+    );
+    assert_eq!(accelerator.to_display_string(DisplayStyle::MacOs), "✦◆⌃⇧K");
+}
+
+#[test]
+fn test_modifier_sides() {
+    let accelerator = parse_accelerator("LeftCtrl+RightShift+KeyS").unwrap();
+    let expected = Accelerator::new(None, Code::KeyS).with_sides(ModifierSides {
+        left: Modifiers::CONTROL,
+        right: Modifiers::SHIFT,
+    });
+    assert_eq!(accelerator, expected);
+    assert_eq!(accelerator.to_string(), "LeftCtrl+RightShift+KeyS");
+    let either_side = parse_accelerator("Ctrl+Shift+KeyS").unwrap();
+    assert_ne!(accelerator, either_side);
+    assert_ne!(accelerator.id(), either_side.id());
+
+    let mods = Modifiers::CONTROL | Modifiers::SHIFT;
+    assert!(accelerator.matches(mods, Code::KeyS));
+    assert!(accelerator.matches_with_sides(
+        mods,
+        ModifierSides {
+            left: Modifiers::CONTROL | Modifiers::SHIFT,
+            right: Modifiers::SHIFT,
+        },
+        Code::KeyS
+    ));
+    assert!(!accelerator.matches_with_sides(
+        mods,
+        ModifierSides {
+            left: Modifiers::SHIFT,
+            right: Modifiers::CONTROL,
+        },
+        Code::KeyS
+    ));
+
+    let accelerator = parse_accelerator("LeftAlt+RightAlt+'a'").unwrap();
+    assert_eq!(accelerator.to_string(), "LeftAlt+RightAlt+'a'");
+    assert_eq!(
+        accelerator.sides(),
+        ModifierSides {
+            left: Modifiers::ALT,
+            right: Modifiers::ALT
+        }
+    );
+}
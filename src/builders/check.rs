@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{accelerator::Accelerator, CheckMenuItem};
+use crate::{accelerator::Accelerator, icon::Icon, CheckMenuItem};
 
 /// A builder type for [`CheckMenuItem`]
 #[derive(Clone, Debug, Default)]
@@ -11,6 +11,7 @@ pub struct CheckMenuItemBuilder {
     enabled: bool,
     checked: bool,
     acccelerator: Option<Accelerator>,
+    icon: Option<Icon>,
 }
 
 impl CheckMenuItemBuilder {
@@ -38,6 +39,14 @@ impl CheckMenuItemBuilder {
         self
     }
 
+    /// Set this check menu item icon.
+    ///
+    /// See [`CheckMenuItem::set_icon`] for more info.
+    pub fn icon(mut self, icon: Option<Icon>) -> Self {
+        self.icon = icon;
+        self
+    }
+
     /// Set this check menu item accelerator.
     pub fn acccelerator<A: TryInto<Accelerator>>(
         mut self,
@@ -52,6 +61,10 @@ impl CheckMenuItemBuilder {
 
     /// Build this check menu item.
     pub fn build(self) -> CheckMenuItem {
-        CheckMenuItem::new(self.text, self.enabled, self.checked, self.acccelerator)
+        let item = CheckMenuItem::new(self.text, self.enabled, self.checked, self.acccelerator);
+        if self.icon.is_some() {
+            item.set_icon(self.icon);
+        }
+        item
     }
 }
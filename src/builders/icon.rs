@@ -16,6 +16,7 @@ pub struct IconMenuItemBuilder {
     acccelerator: Option<Accelerator>,
     icon: Option<Icon>,
     native_icon: Option<NativeIcon>,
+    icon_name: Option<String>,
 }
 
 impl IconMenuItemBuilder {
@@ -41,6 +42,7 @@ impl IconMenuItemBuilder {
     pub fn icon(mut self, icon: Option<Icon>) -> Self {
         self.icon = icon;
         self.native_icon = None;
+        self.icon_name = None;
         self
     }
 
@@ -48,6 +50,17 @@ impl IconMenuItemBuilder {
     pub fn native_icon(mut self, icon: Option<NativeIcon>) -> Self {
         self.native_icon = icon;
         self.icon = None;
+        self.icon_name = None;
+        self
+    }
+
+    /// Set this icon menu item icon from the current icon theme.
+    ///
+    /// See [`IconMenuItem::with_icon_name`] for more info.
+    pub fn icon_name<S: Into<String>>(mut self, icon_name: Option<S>) -> Self {
+        self.icon_name = icon_name.map(Into::into);
+        self.icon = None;
+        self.native_icon = None;
         self
     }
 
@@ -67,6 +80,13 @@ impl IconMenuItemBuilder {
     pub fn build(self) -> IconMenuItem {
         if self.icon.is_some() {
             IconMenuItem::new(self.text, self.enabled, self.icon, self.acccelerator)
+        } else if self.icon_name.is_some() {
+            IconMenuItem::with_icon_name(
+                self.text,
+                self.enabled,
+                self.icon_name.as_deref(),
+                self.acccelerator,
+            )
         } else {
             IconMenuItem::with_native_icon(
                 self.text,
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{IsMenuItem, Submenu};
+use crate::{icon::Icon, IsMenuItem, Submenu};
 
 /// A builder type for [`Submenu`]
 #[derive(Clone, Default)]
@@ -10,6 +10,7 @@ pub struct SubmenuBuilder<'a> {
     text: String,
     enabled: bool,
     items: Vec<&'a dyn IsMenuItem>,
+    icon: Option<Icon>,
 }
 
 impl std::fmt::Debug for SubmenuBuilder<'_> {
@@ -17,6 +18,7 @@ impl std::fmt::Debug for SubmenuBuilder<'_> {
         f.debug_struct("SubmenuBuilder")
             .field("text", &self.text)
             .field("enabled", &self.enabled)
+            .field("icon", &self.icon)
             .finish()
     }
 }
@@ -40,6 +42,14 @@ impl<'a> SubmenuBuilder<'a> {
         self
     }
 
+    /// Set this submenu icon.
+    ///
+    /// See [`Submenu::set_icon`] for more info.
+    pub fn icon(mut self, icon: Option<Icon>) -> Self {
+        self.icon = icon;
+        self
+    }
+
     /// Add an item to this submenu.
     pub fn item(mut self, item: &'a dyn IsMenuItem) -> Self {
         self.items.push(item);
@@ -54,6 +64,10 @@ impl<'a> SubmenuBuilder<'a> {
 
     /// Build this menu item.
     pub fn build(self) -> crate::Result<Submenu> {
-        Submenu::with_items(self.text, self.enabled, &self.items)
+        let submenu = Submenu::with_items(self.text, self.enabled, &self.items)?;
+        if self.icon.is_some() {
+            submenu.set_icon(self.icon);
+        }
+        Ok(submenu)
     }
 }
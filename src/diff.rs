@@ -0,0 +1,890 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Menu diffing, to keep a menu in sync with a declarative description of it.
+//!
+//! Applications computing the menu they want from their state can describe it with
+//! [`ItemDescription`]s, identified by stable keys chosen by the application,
+//! and let a [`DeclarativeMenu`] update the native menu with the fewest changes,
+//! instead of rebuilding it, which causes flicker and closes open submenus:
+//!
+//! ```no_run
+//! # use muda::diff::{DeclarativeMenu, ItemDescription};
+//! fn describe(can_undo: bool, recent: &[&str]) -> Vec<ItemDescription> {
+//!     vec![
+//!         ItemDescription::submenu(
+//!             "file",
+//!             "&File",
+//!             recent
+//!                 .iter()
+//!                 .map(|path| ItemDescription::menu_item(format!("file.recent.{path}"), *path))
+//!                 .collect(),
+//!         ),
+//!         ItemDescription::submenu(
+//!             "edit",
+//!             "&Edit",
+//!             vec![ItemDescription::menu_item("edit.undo", "Undo").enabled(can_undo)],
+//!         ),
+//!     ]
+//! }
+//!
+//! let mut menu = DeclarativeMenu::new();
+//! menu.update(describe(false, &["a.txt"])).unwrap();
+//! // only enables "Undo" and inserts "b.txt"
+//! let ops = menu.update(describe(true, &["b.txt", "a.txt"])).unwrap();
+//! assert_eq!(ops.len(), 2);
+//! ```
+//!
+//! The operations can also be computed with [`diff`] and applied later with [`DeclarativeMenu::apply`].
+
+use std::collections::HashMap;
+
+use crate::{
+    accelerator::Accelerator, icon::Icon, CheckMenuItem, IconMenuItem, IsMenuItem, Menu, MenuItem,
+    MenuItemKind, PredefinedMenuItem, Submenu,
+};
+
+/// The description of a menu item, see the [module docs](self).
+///
+/// Keys have to be unique across a whole menu, including its submenus.
+#[derive(Debug, Clone)]
+pub struct ItemDescription {
+    key: String,
+    kind: DescriptionKind,
+    text: String,
+    enabled: bool,
+    accelerator: Option<Accelerator>,
+    icon: Option<(String, Icon)>,
+}
+
+#[derive(Debug, Clone)]
+enum DescriptionKind {
+    MenuItem,
+    Check(bool),
+    Icon,
+    Submenu(Vec<ItemDescription>),
+    Separator,
+}
+
+impl ItemDescription {
+    fn new<K: Into<String>, S: Into<String>>(key: K, text: S, kind: DescriptionKind) -> Self {
+        Self {
+            key: key.into(),
+            kind,
+            text: text.into(),
+            enabled: true,
+            accelerator: None,
+            icon: None,
+        }
+    }
+
+    /// Describes an enabled [`MenuItem`].
+    pub fn menu_item<K: Into<String>, S: Into<String>>(key: K, text: S) -> Self {
+        Self::new(key, text, DescriptionKind::MenuItem)
+    }
+
+    /// Describes an enabled [`CheckMenuItem`].
+    pub fn check_menu_item<K: Into<String>, S: Into<String>>(
+        key: K,
+        text: S,
+        checked: bool,
+    ) -> Self {
+        Self::new(key, text, DescriptionKind::Check(checked))
+    }
+
+    /// Describes an enabled [`IconMenuItem`], see [`ItemDescription::icon`] to set its icon.
+    pub fn icon_menu_item<K: Into<String>, S: Into<String>>(key: K, text: S) -> Self {
+        Self::new(key, text, DescriptionKind::Icon)
+    }
+
+    /// Describes an enabled [`Submenu`] with `items`.
+    pub fn submenu<K: Into<String>, S: Into<String>>(
+        key: K,
+        text: S,
+        items: Vec<ItemDescription>,
+    ) -> Self {
+        Self::new(key, text, DescriptionKind::Submenu(items))
+    }
+
+    /// Describes a separator, see [`PredefinedMenuItem::separator`].
+    pub fn separator<K: Into<String>>(key: K) -> Self {
+        Self::new(key, "", DescriptionKind::Separator)
+    }
+
+    /// Enable or disable this item.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the accelerator of this item, ignored for submenus and separators.
+    pub fn accelerator(mut self, accelerator: Option<Accelerator>) -> Self {
+        self.accelerator = accelerator;
+        self
+    }
+
+    /// Set the icon of this item, ignored for menu items and separators.
+    ///
+    /// Icons can't be compared, so they are identified by `name`
+    /// and an item's icon is only updated when its name changes.
+    pub fn icon<S: Into<String>>(mut self, name: S, icon: Icon) -> Self {
+        self.icon = Some((name.into(), icon));
+        self
+    }
+
+    /// Returns the key of this item.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the text of this item.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns whether this item is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the checked state of this item if it describes a [`CheckMenuItem`].
+    pub fn checked(&self) -> Option<bool> {
+        match self.kind {
+            DescriptionKind::Check(checked) => Some(checked),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the icon of this item.
+    pub fn icon_name(&self) -> Option<&str> {
+        self.icon.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the items of this item if it describes a [`Submenu`].
+    pub fn items(&self) -> &[ItemDescription] {
+        match &self.kind {
+            DescriptionKind::Submenu(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn is_same_kind(&self, other: &ItemDescription) -> bool {
+        std::mem::discriminant(&self.kind) == std::mem::discriminant(&other.kind)
+    }
+
+    fn has_accelerator(&self) -> bool {
+        matches!(
+            self.kind,
+            DescriptionKind::MenuItem | DescriptionKind::Check(_) | DescriptionKind::Icon
+        )
+    }
+
+    fn has_icon(&self) -> bool {
+        matches!(
+            self.kind,
+            DescriptionKind::Check(_) | DescriptionKind::Icon | DescriptionKind::Submenu(_)
+        )
+    }
+}
+
+/// An operation on a menu, as returned by [`diff`].
+///
+/// Items are identified by their keys and positions are indices among their siblings.
+#[derive(Debug, Clone)]
+pub enum MenuOp {
+    /// Inserts `item` and its descendants at `position` in the submenu with the key `parent`,
+    /// or in the menu itself if `None`.
+    Insert {
+        parent: Option<String>,
+        position: usize,
+        item: ItemDescription,
+    },
+    /// Removes the item with `key` and its descendants.
+    Remove { key: String },
+    /// Moves the item with `key` to `position` among its siblings,
+    /// counted after removing the item from its current position.
+    Move { key: String, position: usize },
+    /// Sets the text of the item with `key`.
+    SetText { key: String, text: String },
+    /// Enables or disables the item with `key`.
+    SetEnabled { key: String, enabled: bool },
+    /// Checks or unchecks the check menu item with `key`.
+    SetChecked { key: String, checked: bool },
+    /// Sets the accelerator of the item with `key`.
+    SetAccelerator {
+        key: String,
+        accelerator: Option<Accelerator>,
+    },
+    /// Sets the icon of the item with `key`, along with its name, see [`ItemDescription::icon`].
+    SetIcon {
+        key: String,
+        icon: Option<(String, Icon)>,
+    },
+}
+
+/// Returns the operations turning a menu described by `old` into one described by `new`.
+///
+/// Items are matched by key and kind, an item whose kind or parent changed is removed
+/// and inserted again. All removals come first, then, for every submenu, the moves,
+/// as few as possible, the insertions and the changes of the remaining items.
+pub fn diff(old: &[ItemDescription], new: &[ItemDescription]) -> Vec<MenuOp> {
+    let mut ops = Vec::new();
+    diff_removals(old, new, &mut ops);
+    diff_items(None, old, new, &mut ops);
+    ops
+}
+
+fn find_same<'a>(
+    items: &'a [ItemDescription],
+    item: &ItemDescription,
+) -> Option<&'a ItemDescription> {
+    items
+        .iter()
+        .find(|i| i.key == item.key && i.is_same_kind(item))
+}
+
+fn diff_removals(old: &[ItemDescription], new: &[ItemDescription], ops: &mut Vec<MenuOp>) {
+    for item in old {
+        match find_same(new, item) {
+            Some(new_item) => diff_removals(item.items(), new_item.items(), ops),
+            None => ops.push(MenuOp::Remove {
+                key: item.key.clone(),
+            }),
+        }
+    }
+}
+
+fn diff_items(
+    parent: Option<&str>,
+    old: &[ItemDescription],
+    new: &[ItemDescription],
+    ops: &mut Vec<MenuOp>,
+) {
+    // the kept items, in their current order and in the order they should have
+    let mut current = old
+        .iter()
+        .filter(|item| find_same(new, item).is_some())
+        .map(|item| item.key.as_str())
+        .collect::<Vec<_>>();
+    let target = new
+        .iter()
+        .filter(|item| find_same(old, item).is_some())
+        .map(|item| item.key.as_str())
+        .collect::<Vec<_>>();
+
+    // the longest run of items already in order stays in place, and every other item
+    // is moved right after the item preceding it in `target`, which was either
+    // in place already or moved before
+    let positions = target
+        .iter()
+        .map(|key| current.iter().position(|k| k == key).unwrap_or_default())
+        .collect::<Vec<_>>();
+    let in_place = longest_increasing_subsequence(&positions);
+    for (i, key) in target.iter().enumerate() {
+        if in_place.contains(&i) {
+            continue;
+        }
+
+        let Some(from) = current.iter().position(|k| k == key) else {
+            continue;
+        };
+        current.remove(from);
+        let position = match i {
+            0 => 0,
+            _ => current
+                .iter()
+                .position(|k| *k == target[i - 1])
+                .map_or(0, |p| p + 1),
+        };
+        current.insert(position, key);
+        if position != from {
+            ops.push(MenuOp::Move {
+                key: key.to_string(),
+                position,
+            });
+        }
+    }
+
+    for (position, item) in new.iter().enumerate() {
+        if find_same(old, item).is_none() {
+            ops.push(MenuOp::Insert {
+                parent: parent.map(str::to_string),
+                position,
+                item: item.clone(),
+            });
+        }
+    }
+
+    for item in new {
+        if let Some(old_item) = find_same(old, item) {
+            diff_properties(old_item, item, ops);
+            diff_items(Some(&item.key), old_item.items(), item.items(), ops);
+        }
+    }
+}
+
+fn diff_properties(old: &ItemDescription, new: &ItemDescription, ops: &mut Vec<MenuOp>) {
+    let key = || new.key.clone();
+
+    if let DescriptionKind::Separator = new.kind {
+        return;
+    }
+    if old.text != new.text {
+        ops.push(MenuOp::SetText {
+            key: key(),
+            text: new.text.clone(),
+        });
+    }
+    if old.enabled != new.enabled {
+        ops.push(MenuOp::SetEnabled {
+            key: key(),
+            enabled: new.enabled,
+        });
+    }
+    if let (Some(old_checked), Some(checked)) = (old.checked(), new.checked()) {
+        if old_checked != checked {
+            ops.push(MenuOp::SetChecked {
+                key: key(),
+                checked,
+            });
+        }
+    }
+    if new.has_accelerator() && old.accelerator != new.accelerator {
+        ops.push(MenuOp::SetAccelerator {
+            key: key(),
+            accelerator: new.accelerator,
+        });
+    }
+    if new.has_icon() && old.icon_name() != new.icon_name() {
+        ops.push(MenuOp::SetIcon {
+            key: key(),
+            icon: new.icon.clone(),
+        });
+    }
+}
+
+/// Returns the indices of a longest strictly increasing subsequence of `values`.
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<usize> {
+    // `tails[n]` is the index of the smallest value ending an increasing subsequence of length `n + 1`
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (i, value) in values.iter().enumerate() {
+        let len = tails.partition_point(|t| values[*t] < *value);
+        if len > 0 {
+            previous[i] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+
+    let mut indices = Vec::new();
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        indices.push(i);
+        next = previous[i];
+    }
+    indices.reverse();
+    indices
+}
+
+/// A [`Menu`] kept in sync with an [`ItemDescription`] list, see the [module docs](self).
+///
+/// Items of the menu should only be added and removed through this type.
+pub struct DeclarativeMenu {
+    menu: Menu,
+    description: Vec<ItemDescription>,
+    /// The parent key and item of every key.
+    items: HashMap<String, (Option<String>, MenuItemKind)>,
+}
+
+impl Default for DeclarativeMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeclarativeMenu {
+    /// Creates a new empty menu.
+    pub fn new() -> Self {
+        Self {
+            menu: Menu::new(),
+            description: Vec::new(),
+            items: HashMap::new(),
+        }
+    }
+
+    /// Returns the menu, to add it to a window or app.
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    /// Returns the current description of the menu.
+    pub fn description(&self) -> &[ItemDescription] {
+        &self.description
+    }
+
+    /// Returns the item with `key`.
+    pub fn item(&self, key: &str) -> Option<&MenuItemKind> {
+        self.items.get(key).map(|(_, item)| item)
+    }
+
+    /// Returns the key of the item with `id`, for example to handle a [`MenuEvent`](crate::MenuEvent).
+    pub fn key(&self, id: u32) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|(_, (_, item))| item.id() == id)
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Updates the menu to match `description` and returns the applied operations, see [`diff`].
+    ///
+    /// The text, enabled and checked state of the items are compared as they currently are,
+    /// so changes made on them directly, like a check item toggled by a click, are reverted.
+    ///
+    /// Returns [`Error::DuplicateItemKey`](crate::Error::DuplicateItemKey) without changing
+    /// anything if a key is used more than once.
+    pub fn update(&mut self, description: Vec<ItemDescription>) -> crate::Result<Vec<MenuOp>> {
+        let mut keys = Vec::new();
+        collect_keys(&description, &mut keys);
+        keys.sort_unstable();
+        if let Some(key) = keys.windows(2).find(|k| k[0] == k[1]) {
+            return Err(crate::Error::DuplicateItemKey(key[0].to_string()));
+        }
+
+        // items may have been changed directly, like a check item toggled by a click,
+        // so the diff starts from their current state to restore the described one
+        refresh_descriptions(&mut self.description, &self.items);
+        let ops = diff(&self.description, &description);
+        self.apply(&ops)?;
+        self.description = description;
+        Ok(ops)
+    }
+
+    /// Applies `ops`, in order, to the menu and its description.
+    ///
+    /// Stops at the first operation that fails, leaving the previous ones applied.
+    pub fn apply(&mut self, ops: &[MenuOp]) -> crate::Result<()> {
+        for op in ops {
+            self.apply_op(op)?;
+        }
+        Ok(())
+    }
+
+    fn apply_op(&mut self, op: &MenuOp) -> crate::Result<()> {
+        match op {
+            MenuOp::Insert {
+                parent,
+                position,
+                item,
+            } => {
+                let mut keys = Vec::new();
+                collect_keys(std::slice::from_ref(item), &mut keys);
+                if let Some(key) = keys.iter().find(|k| self.items.contains_key(**k)) {
+                    return Err(crate::Error::DuplicateItemKey(key.to_string()));
+                }
+
+                let parent = parent.as_deref();
+                let position = (*position).min(self.siblings(parent)?.len());
+                let inserted = self
+                    .create_item(parent, item)
+                    .and_then(|new_item| self.insert_item(parent, &new_item, position));
+                if let Err(e) = inserted {
+                    for key in keys {
+                        self.items.remove(key);
+                    }
+                    return Err(e);
+                }
+                self.siblings(parent)?.insert(position, item.clone());
+            }
+            MenuOp::Remove { key } => {
+                let (parent, item) = self.entry(key)?;
+                self.remove_item(parent.as_deref(), &item)?;
+
+                let siblings = self.siblings(parent.as_deref())?;
+                if let Some(i) = siblings.iter().position(|i| i.key == *key) {
+                    let removed = siblings.remove(i);
+                    let mut keys = Vec::new();
+                    collect_keys(std::slice::from_ref(&removed), &mut keys);
+                    for key in keys {
+                        self.items.remove(key);
+                    }
+                }
+            }
+            MenuOp::Move { key, position } => {
+                let (parent, item) = self.entry(key)?;
+                let parent = parent.as_deref();
+                self.remove_item(parent, &item)?;
+
+                let siblings = self.siblings(parent)?;
+                let moved = siblings
+                    .iter()
+                    .position(|i| i.key == *key)
+                    .map(|i| siblings.remove(i));
+                let position = (*position).min(siblings.len());
+                if let Some(moved) = moved {
+                    siblings.insert(position, moved);
+                }
+                self.insert_item(parent, &item, position)?;
+            }
+            MenuOp::SetText { key, text } => {
+                match self.entry(key)?.1 {
+                    MenuItemKind::MenuItem(i) => i.set_text(text),
+                    MenuItemKind::Submenu(i) => i.set_text(text),
+                    MenuItemKind::Predefined(i) => i.set_text(text),
+                    MenuItemKind::Check(i) => i.set_text(text),
+                    MenuItemKind::Icon(i) => i.set_text(text),
+                }
+                self.find_description(key)?.text = text.clone();
+            }
+            MenuOp::SetEnabled { key, enabled } => {
+                match self.entry(key)?.1 {
+                    MenuItemKind::MenuItem(i) => i.set_enabled(*enabled),
+                    MenuItemKind::Submenu(i) => i.set_enabled(*enabled),
+                    MenuItemKind::Check(i) => i.set_enabled(*enabled),
+                    MenuItemKind::Icon(i) => i.set_enabled(*enabled),
+                    MenuItemKind::Predefined(_) => {}
+                }
+                self.find_description(key)?.enabled = *enabled;
+            }
+            MenuOp::SetChecked { key, checked } => {
+                if let MenuItemKind::Check(i) = self.entry(key)?.1 {
+                    i.set_checked(*checked);
+                }
+                let description = self.find_description(key)?;
+                if let DescriptionKind::Check(c) = &mut description.kind {
+                    *c = *checked;
+                }
+            }
+            MenuOp::SetAccelerator { key, accelerator } => {
+                match self.entry(key)?.1 {
+                    MenuItemKind::MenuItem(i) => i.set_accelerator(*accelerator)?,
+                    MenuItemKind::Check(i) => i.set_accelerator(*accelerator)?,
+                    MenuItemKind::Icon(i) => i.set_accelerator(*accelerator)?,
+                    MenuItemKind::Submenu(_) | MenuItemKind::Predefined(_) => {
+                        return Err(crate::Error::AcceleratorNotSupported)
+                    }
+                }
+                self.find_description(key)?.accelerator = *accelerator;
+            }
+            MenuOp::SetIcon { key, icon } => {
+                let native_icon = icon.as_ref().map(|(_, icon)| icon.clone());
+                match self.entry(key)?.1 {
+                    MenuItemKind::Submenu(i) => i.set_icon(native_icon),
+                    MenuItemKind::Check(i) => i.set_icon(native_icon),
+                    MenuItemKind::Icon(i) => i.set_icon(native_icon),
+                    MenuItemKind::MenuItem(_) | MenuItemKind::Predefined(_) => {}
+                }
+                self.find_description(key)?.icon = icon.clone();
+            }
+        }
+        Ok(())
+    }
+
+    fn entry(&self, key: &str) -> crate::Result<(Option<String>, MenuItemKind)> {
+        self.items
+            .get(key)
+            .cloned()
+            .ok_or_else(|| crate::Error::UnknownItemKey(key.to_string()))
+    }
+
+    fn submenu(&self, key: &str) -> crate::Result<Submenu> {
+        match self.entry(key)?.1 {
+            MenuItemKind::Submenu(submenu) => Ok(submenu),
+            _ => Err(crate::Error::UnknownItemKey(key.to_string())),
+        }
+    }
+
+    fn siblings(&mut self, parent: Option<&str>) -> crate::Result<&mut Vec<ItemDescription>> {
+        let Some(parent) = parent else {
+            return Ok(&mut self.description);
+        };
+        match &mut self.find_description(parent)?.kind {
+            DescriptionKind::Submenu(items) => Ok(items),
+            _ => Err(crate::Error::UnknownItemKey(parent.to_string())),
+        }
+    }
+
+    fn find_description(&mut self, key: &str) -> crate::Result<&mut ItemDescription> {
+        find_description(&mut self.description, key)
+            .ok_or_else(|| crate::Error::UnknownItemKey(key.to_string()))
+    }
+
+    fn insert_item(
+        &self,
+        parent: Option<&str>,
+        item: &MenuItemKind,
+        position: usize,
+    ) -> crate::Result<()> {
+        match parent {
+            Some(parent) => self.submenu(parent)?.insert(item.as_ref(), position),
+            None => self.menu.insert(item.as_ref(), position),
+        }
+    }
+
+    fn remove_item(&self, parent: Option<&str>, item: &MenuItemKind) -> crate::Result<()> {
+        match parent {
+            Some(parent) => self.submenu(parent)?.remove(item.as_ref()),
+            None => self.menu.remove(item.as_ref()),
+        }
+    }
+
+    /// Creates the item described by `description` and its descendants, and records their keys.
+    fn create_item(
+        &mut self,
+        parent: Option<&str>,
+        description: &ItemDescription,
+    ) -> crate::Result<MenuItemKind> {
+        let icon = description.icon.as_ref().map(|(_, icon)| icon.clone());
+        let item = match &description.kind {
+            DescriptionKind::MenuItem => MenuItem::new(
+                &description.text,
+                description.enabled,
+                description.accelerator,
+            )
+            .kind(),
+            DescriptionKind::Check(checked) => {
+                let item = CheckMenuItem::new(
+                    &description.text,
+                    description.enabled,
+                    *checked,
+                    description.accelerator,
+                );
+                if icon.is_some() {
+                    item.set_icon(icon);
+                }
+                item.kind()
+            }
+            DescriptionKind::Icon => IconMenuItem::new(
+                &description.text,
+                description.enabled,
+                icon,
+                description.accelerator,
+            )
+            .kind(),
+            DescriptionKind::Submenu(items) => {
+                let submenu = Submenu::new(&description.text, description.enabled);
+                if icon.is_some() {
+                    submenu.set_icon(icon);
+                }
+                for item in items {
+                    let child = self.create_item(Some(&description.key), item)?;
+                    submenu.append(child.as_ref())?;
+                }
+                submenu.kind()
+            }
+            DescriptionKind::Separator => PredefinedMenuItem::separator().kind(),
+        };
+
+        self.items.insert(
+            description.key.clone(),
+            (parent.map(str::to_string), item.clone()),
+        );
+        Ok(item)
+    }
+}
+
+/// Reads the text, enabled and checked state of `items` back from their menu items.
+fn refresh_descriptions(
+    items: &mut [ItemDescription],
+    entries: &HashMap<String, (Option<String>, MenuItemKind)>,
+) {
+    for description in items {
+        if let Some((_, item)) = entries.get(&description.key) {
+            let (text, enabled) = match item {
+                MenuItemKind::MenuItem(i) => (i.text(), i.is_enabled()),
+                MenuItemKind::Submenu(i) => (i.text(), i.is_enabled()),
+                MenuItemKind::Check(i) => (i.text(), i.is_enabled()),
+                MenuItemKind::Icon(i) => (i.text(), i.is_enabled()),
+                MenuItemKind::Predefined(_) => (description.text.clone(), description.enabled),
+            };
+            description.text = text;
+            description.enabled = enabled;
+            if let (MenuItemKind::Check(i), DescriptionKind::Check(checked)) =
+                (item, &mut description.kind)
+            {
+                *checked = i.is_checked();
+            }
+        }
+        if let DescriptionKind::Submenu(items) = &mut description.kind {
+            refresh_descriptions(items, entries);
+        }
+    }
+}
+
+fn collect_keys<'a>(items: &'a [ItemDescription], keys: &mut Vec<&'a str>) {
+    for item in items {
+        keys.push(&item.key);
+        collect_keys(item.items(), keys);
+    }
+}
+
+fn find_description<'a>(
+    items: &'a mut [ItemDescription],
+    key: &str,
+) -> Option<&'a mut ItemDescription> {
+    for item in items {
+        if item.key == key {
+            return Some(item);
+        }
+        if let DescriptionKind::Submenu(items) = &mut item.kind {
+            if let Some(item) = find_description(items, key) {
+                return Some(item);
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn test_diff() {
+    let item = ItemDescription::menu_item;
+    let keys = |ops: &[MenuOp]| {
+        ops.iter()
+            .map(|op| match op {
+                MenuOp::Insert { item, position, .. } => format!("insert {} {position}", item.key),
+                MenuOp::Remove { key } => format!("remove {key}"),
+                MenuOp::Move { key, position } => format!("move {key} {position}"),
+                MenuOp::SetText { key, text } => format!("text {key} {text}"),
+                MenuOp::SetEnabled { key, enabled } => format!("enabled {key} {enabled}"),
+                MenuOp::SetChecked { key, checked } => format!("checked {key} {checked}"),
+                MenuOp::SetAccelerator { key, .. } => format!("accelerator {key}"),
+                MenuOp::SetIcon { key, .. } => format!("icon {key}"),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let abcd = ["a", "b", "c", "d"].map(|k| item(k, k)).to_vec();
+    assert!(diff(&abcd, &abcd).is_empty());
+
+    let bcda = ["b", "c", "d", "a"].map(|k| item(k, k)).to_vec();
+    assert_eq!(keys(&diff(&abcd, &bcda)), ["move a 3"]);
+    let dabc = ["d", "a", "b", "c"].map(|k| item(k, k)).to_vec();
+    assert_eq!(keys(&diff(&abcd, &dabc)), ["move d 0"]);
+    let bdxa = vec![
+        item("b", "b"),
+        ItemDescription::separator("d"),
+        item("x", "x"),
+        item("a", "A").enabled(false),
+    ];
+    assert_eq!(
+        keys(&diff(&abcd, &bdxa)),
+        [
+            "remove c",
+            "remove d",
+            "move b 0",
+            "insert d 1",
+            "insert x 2",
+            "text a A",
+            "enabled a false"
+        ]
+    );
+
+    let old = vec![
+        ItemDescription::submenu(
+            "file",
+            "File",
+            vec![item("open", "Open"), item("save", "Save")],
+        ),
+        ItemDescription::submenu("view", "View", vec![]),
+    ];
+    let new = vec![
+        ItemDescription::submenu("file", "File", vec![item("open", "Open")]),
+        ItemDescription::submenu(
+            "view",
+            "View",
+            vec![
+                item("save", "Save"),
+                ItemDescription::check_menu_item("wrap", "Wrap", true),
+            ],
+        ),
+    ];
+    assert_eq!(
+        keys(&diff(&old, &new)),
+        ["remove save", "insert save 0", "insert wrap 1"]
+    );
+
+    assert_eq!(
+        longest_increasing_subsequence(&[3, 0, 4, 1, 2, 5]),
+        [1, 3, 4, 5]
+    );
+}
+
+#[test]
+fn test_declarative_menu() {
+    let describe = |keys: &[&str], wrap: bool| {
+        let items = keys
+            .iter()
+            .map(|k| ItemDescription::menu_item(*k, *k))
+            .collect();
+        vec![
+            ItemDescription::submenu("file", "File", items),
+            ItemDescription::check_menu_item("wrap", "Wrap", wrap),
+        ]
+    };
+    let texts = |menu: &DeclarativeMenu| {
+        menu.item("file")
+            .unwrap()
+            .as_submenu()
+            .unwrap()
+            .items()
+            .iter()
+            .map(|i| i.as_menuitem().unwrap().text())
+            .collect::<Vec<_>>()
+    };
+
+    let mut menu = DeclarativeMenu::new();
+    assert_eq!(
+        menu.update(describe(&["a", "b", "c"], false))
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(texts(&menu), ["a", "b", "c"]);
+
+    let a = menu.item("a").unwrap().id();
+    menu.update(describe(&["c", "a", "d"], true)).unwrap();
+    assert_eq!(texts(&menu), ["c", "a", "d"]);
+    assert_eq!(menu.item("a").unwrap().id(), a);
+    assert_eq!(menu.key(a), Some("a"));
+    assert!(menu.item("b").is_none());
+    assert!(menu
+        .item("wrap")
+        .unwrap()
+        .as_check_menuitem()
+        .unwrap()
+        .is_checked());
+    assert_eq!(menu.description()[0].items().len(), 3);
+
+    // changes made on the items directly are reverted to the description
+    let wrap = menu
+        .item("wrap")
+        .unwrap()
+        .as_check_menuitem()
+        .unwrap()
+        .clone();
+    wrap.set_checked(false);
+    menu.item("a").unwrap().as_menuitem().unwrap().set_text("b");
+    let ops = menu.update(describe(&["c", "a", "d"], true)).unwrap();
+    assert!(matches!(
+        &ops[..],
+        [
+            MenuOp::SetText { key, text },
+            MenuOp::SetChecked { key: wrap_key, checked: true },
+        ] if key == "a" && text == "a" && wrap_key == "wrap"
+    ));
+    assert!(wrap.is_checked());
+    assert_eq!(texts(&menu), ["c", "a", "d"]);
+
+    assert!(matches!(
+        menu.update(describe(&["a", "wrap"], true)),
+        Err(crate::Error::DuplicateItemKey(k)) if k == "wrap"
+    ));
+    assert!(matches!(
+        menu.apply(&[MenuOp::Remove { key: "b".into() }]),
+        Err(crate::Error::UnknownItemKey(_))
+    ));
+}
@@ -22,14 +22,28 @@ pub enum Error {
     #[cfg(target_os = "linux")]
     #[error("This menu has already been initialized for this gtk window`")]
     AlreadyInitialized,
-    #[error("{0}")]
-    AcceleratorParseError(String),
+    #[error(transparent)]
+    AcceleratorParseError(#[from] crate::accelerator::AcceleratorParseError),
     #[error("Couldn't recognize \"{0}\" as a valid Accelerator Code, if you feel like it should be, please report this to https://github.com/tauri-apps/muda")]
     UnrecognizedAcceleratorCode(String),
-    #[error("Unexpected empty token while parsing accelerator: \"{0}\"")]
-    EmptyAcceleratorToken(String),
-    #[error("Unexpected accelerator string format: \"{0}\", a accelerator should have the modifiers first and only contain one main key")]
-    UnexpectedAcceleratorFormat(String),
+    #[error("The accelerator \"{0}\" is already used by another item of this menu")]
+    AcceleratorConflict(crate::accelerator::Accelerator),
+    #[error("This menu item doesn't support accelerators")]
+    AcceleratorNotSupported,
+    #[error("The accelerator modifiers {0:?} are not supported on this platform")]
+    UnsupportedAcceleratorModifiers(crate::accelerator::Modifiers),
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    #[error("Couldn't connect to the X11 server for global hotkeys: {0}")]
+    X11ConnectionFailed(String),
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    #[error("The global hotkey \"{0}\" is already grabbed by another application")]
+    GlobalHotKeyAlreadyGrabbed(crate::accelerator::Accelerator),
+    #[error("Invalid keymap binding on line {0}: \"{1}\", a binding should have the form `id = accelerator`")]
+    KeymapSyntaxError(usize, String),
+    #[error("There is no menu item with the key \"{0}\"")]
+    UnknownItemKey(String),
+    #[error("The key \"{0}\" is used by more than one menu item")]
+    DuplicateItemKey(String),
 }
 
 /// Convenient type alias of Result type for muda.
@@ -34,6 +34,15 @@ pub enum BadIcon {
     },
     /// Produced when underlying OS functionality failed to create the icon
     OsError(io::Error),
+    /// Produced when decoding PNG data failed.
+    PngDecodingError(String),
+    /// Produced when decoding ICO data failed.
+    IcoDecodingError(String),
+    /// Produced when parsing SVG data failed.
+    SvgDecodingError(String),
+    /// Produced when the data isn't in a supported image format,
+    /// or the feature flag for its format isn't enabled.
+    UnsupportedFormat,
 }
 
 impl fmt::Display for BadIcon {
@@ -53,6 +62,10 @@ impl fmt::Display for BadIcon {
                 width, height, pixel_count, width_x_height,
             ),
             BadIcon::OsError(e) => write!(f, "OS error when instantiating the icon: {:?}", e),
+            BadIcon::PngDecodingError(e) => write!(f, "Failed to decode PNG data: {}", e),
+            BadIcon::IcoDecodingError(e) => write!(f, "Failed to decode ICO data: {}", e),
+            BadIcon::SvgDecodingError(e) => write!(f, "Failed to parse SVG data: {}", e),
+            BadIcon::UnsupportedFormat => write!(f, "The icon data is in an unsupported image format."),
         }
     }
 }
@@ -112,10 +125,257 @@ mod constructors {
     }
 }
 
+#[cfg(feature = "image-png")]
+mod decoding {
+    use super::*;
+
+    #[allow(dead_code)] // not used on Windows without `image-ico`
+    pub(crate) const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    pub(crate) fn decode_png(bytes: &[u8]) -> Result<RgbaIcon, BadIcon> {
+        let map_err = |e: png::DecodingError| BadIcon::PngDecodingError(e.to_string());
+
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(map_err)?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).map_err(map_err)?;
+        buf.truncate(info.buffer_size());
+
+        let rgba = match info.color_type {
+            png::ColorType::Rgba => buf,
+            png::ColorType::Rgb => buf
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 0xff])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buf
+                .chunks_exact(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 0xff]).collect(),
+            png::ColorType::Indexed => {
+                return Err(BadIcon::PngDecodingError(
+                    "indexed colors were not expanded".into(),
+                ))
+            }
+        };
+
+        RgbaIcon::from_rgba(rgba, info.width, info.height)
+    }
+
+    /// Decodes all the images of an ICO file, which are either PNG images
+    /// or BMP images without their file header.
+    #[cfg(feature = "image-ico")]
+    pub(crate) fn decode_ico(bytes: &[u8]) -> Result<Vec<RgbaIcon>, BadIcon> {
+        let err = |msg: &str| BadIcon::IcoDecodingError(msg.into());
+
+        if bytes.len() < 6 || read_u16(bytes, 0) != 0 || !matches!(read_u16(bytes, 2), 1 | 2) {
+            return Err(err("invalid ICO header"));
+        }
+
+        let count = read_u16(bytes, 4) as usize;
+        if count == 0 {
+            return Err(err("ICO file contains no images"));
+        }
+
+        (0..count)
+            .map(|i| {
+                let entry = 6 + i * 16;
+                if bytes.len() < entry + 16 {
+                    return Err(err("truncated ICO directory"));
+                }
+                let size = read_u32(bytes, entry + 8) as usize;
+                let offset = read_u32(bytes, entry + 12) as usize;
+                let data = offset
+                    .checked_add(size)
+                    .and_then(|end| bytes.get(offset..end))
+                    .ok_or_else(|| err("ICO image data out of bounds"))?;
+
+                if data.starts_with(PNG_SIGNATURE) {
+                    decode_png(data)
+                } else {
+                    decode_dib(data)
+                }
+            })
+            .collect()
+    }
+
+    /// Decodes a BMP image stored in an ICO file, where the height is doubled to include
+    /// the 1bpp AND mask following the color data.
+    #[cfg(feature = "image-ico")]
+    fn decode_dib(data: &[u8]) -> Result<RgbaIcon, BadIcon> {
+        let err = |msg: &str| BadIcon::IcoDecodingError(msg.into());
+
+        if data.len() < 40 {
+            return Err(err("truncated BMP header"));
+        }
+        let header_size = read_u32(data, 0) as usize;
+        let width = read_u32(data, 4) as i32;
+        let height = read_u32(data, 8) as i32 / 2;
+        let bit_count = read_u16(data, 14) as usize;
+        let compression = read_u32(data, 16);
+        let colors_used = read_u32(data, 32) as usize;
+
+        if width <= 0 || height <= 0 || width > 1024 || height > 1024 {
+            return Err(err("invalid BMP dimensions"));
+        }
+        if compression != 0 {
+            return Err(err("compressed BMP images are not supported"));
+        }
+        if !matches!(bit_count, 1 | 4 | 8 | 24 | 32) {
+            return Err(err("unsupported BMP bit count"));
+        }
+
+        let (width, height) = (width as usize, height as usize);
+        let palette_len = match bit_count {
+            1 | 4 | 8 if colors_used == 0 => 1 << bit_count,
+            1 | 4 | 8 => colors_used,
+            _ => 0,
+        };
+        let palette_start = header_size;
+        let pixels_start = palette_start + palette_len * 4;
+        let row_len = (width * bit_count).div_ceil(32) * 4;
+        let mask_start = pixels_start + row_len * height;
+        let mask_row_len = width.div_ceil(32) * 4;
+        if data.len() < mask_start {
+            return Err(err("truncated BMP data"));
+        }
+        // some encoders omit the AND mask of 32bpp images
+        let has_mask = data.len() >= mask_start + mask_row_len * height;
+
+        let palette = &data[palette_start..pixels_start];
+        let mut rgba = Vec::with_capacity(width * height * PIXEL_SIZE);
+        // rows are stored bottom-up
+        for y in (0..height).rev() {
+            let row = &data[pixels_start + y * row_len..][..row_len];
+            for x in 0..width {
+                let (b, g, r, a) = match bit_count {
+                    32 => (row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]),
+                    24 => (row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 0xff),
+                    _ => {
+                        let bit = x * bit_count;
+                        let index = (row[bit / 8] >> (8 - bit_count - bit % 8))
+                            & ((1 << bit_count) - 1) as u8;
+                        let color = palette
+                            .get(index as usize * 4..index as usize * 4 + 3)
+                            .ok_or_else(|| err("BMP palette index out of bounds"))?;
+                        (color[0], color[1], color[2], 0xff)
+                    }
+                };
+                rgba.extend_from_slice(&[r, g, b, a]);
+            }
+        }
+
+        // the AND mask only matters when there is no alpha channel
+        let has_alpha = bit_count == 32 && rgba.chunks_exact(4).any(|p| p[3] != 0);
+        if has_mask && !has_alpha {
+            for y in 0..height {
+                let row = &data[mask_start + (height - 1 - y) * mask_row_len..][..mask_row_len];
+                for x in 0..width {
+                    let transparent = row[x / 8] & (0x80 >> (x % 8)) != 0;
+                    rgba[(y * width + x) * PIXEL_SIZE + 3] = if transparent { 0 } else { 0xff };
+                }
+            }
+        }
+
+        RgbaIcon::from_rgba(rgba, width as u32, height as u32)
+    }
+
+    #[cfg(feature = "image-ico")]
+    fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    #[cfg(feature = "image-ico")]
+    fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    }
+}
+
+/// An SVG image, rasterized on demand at the size each platform needs.
+#[cfg(feature = "svg")]
+#[derive(Clone)]
+pub(crate) struct SvgIcon {
+    tree: std::sync::Arc<resvg::usvg::Tree>,
+    cache: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<(u32, u32), RgbaIcon>>>,
+}
+
+#[cfg(feature = "svg")]
+impl SvgIcon {
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, BadIcon> {
+        let tree = resvg::usvg::Tree::from_data(bytes, &resvg::usvg::Options::default())
+            .map_err(|e| BadIcon::SvgDecodingError(e.to_string()))?;
+        Ok(Self {
+            tree: std::sync::Arc::new(tree),
+            cache: Default::default(),
+        })
+    }
+
+    /// The size of this image as specified by the SVG data, in pixels.
+    pub(crate) fn size(&self) -> (u32, u32) {
+        let size = self.tree.size();
+        (
+            (size.width().ceil() as u32).max(1),
+            (size.height().ceil() as u32).max(1),
+        )
+    }
+
+    /// Rasterizes this image stretched to `width`x`height` pixels,
+    /// reusing previous results for the same size.
+    pub(crate) fn rasterize(&self, width: u32, height: u32) -> RgbaIcon {
+        let (width, height) = (width.max(1), height.max(1));
+        self.cache
+            .lock()
+            .unwrap()
+            .entry((width, height))
+            .or_insert_with(|| {
+                let size = self.tree.size();
+                let transform = resvg::tiny_skia::Transform::from_scale(
+                    width as f32 / size.width(),
+                    height as f32 / size.height(),
+                );
+                let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height).unwrap();
+                resvg::render(&self.tree, transform, &mut pixmap.as_mut());
+
+                let rgba = pixmap
+                    .pixels()
+                    .iter()
+                    .flat_map(|p| {
+                        let c = p.demultiply();
+                        [c.red(), c.green(), c.blue(), c.alpha()]
+                    })
+                    .collect();
+                RgbaIcon {
+                    rgba,
+                    width,
+                    height,
+                }
+            })
+            .clone()
+    }
+}
+
+#[cfg(feature = "svg")]
+impl fmt::Debug for SvgIcon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SvgIcon")
+            .field("size", &self.size())
+            .finish_non_exhaustive()
+    }
+}
+
 /// An icon used for the window titlebar, taskbar, etc.
 #[derive(Clone)]
 pub struct Icon {
     pub(crate) inner: PlatformIcon,
+    /// Variant used instead of `inner` on dark themes.
+    pub(crate) dark: Option<PlatformIcon>,
+    pub(crate) is_template: bool,
 }
 
 impl fmt::Debug for Icon {
@@ -125,14 +385,145 @@ impl fmt::Debug for Icon {
 }
 
 impl Icon {
+    fn from_platform_icon(inner: PlatformIcon) -> Self {
+        Icon {
+            inner,
+            dark: None,
+            is_template: false,
+        }
+    }
+
     /// Creates an icon from 32bpp RGBA data.
     ///
     /// The length of `rgba` must be divisible by 4, and `width * height` must equal
     /// `rgba.len() / 4`. Otherwise, this will return a `BadIcon` error.
     pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
-        Ok(Icon {
-            inner: PlatformIcon::from_rgba(rgba, width, height)?,
-        })
+        Ok(Self::from_platform_icon(PlatformIcon::from_rgba(
+            rgba, width, height,
+        )?))
+    }
+
+    /// Creates an icon from SVG data, rasterized at the exact size needed
+    /// wherever the icon is shown, so it looks crisp at any scale factor.
+    ///
+    /// Text in the SVG data isn't rendered.
+    #[cfg(feature = "svg")]
+    pub fn from_svg(bytes: &[u8]) -> Result<Self, BadIcon> {
+        Ok(Self::from_platform_icon(PlatformIcon::from_svg(
+            SvgIcon::from_bytes(bytes)?,
+        )?))
+    }
+
+    /// Adds another resolution of this icon from 32bpp RGBA data,
+    /// so that the one best matching the display's scale factor can be used.
+    ///
+    /// See [`Icon::from_rgba`] for the requirements on `rgba`.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / macOS**: Only the resolution the icon was created with is used.
+    pub fn add_rgba(&mut self, rgba: Vec<u8>, width: u32, height: u32) -> Result<(), BadIcon> {
+        let _ = RgbaIcon::from_rgba(rgba.clone(), width, height)?;
+        #[cfg(target_os = "linux")]
+        self.inner.add_rgba(rgba, width, height)?;
+        Ok(())
+    }
+
+    /// Creates an icon from the bytes of a PNG image.
+    #[cfg(feature = "image-png")]
+    pub fn from_png_bytes(bytes: &[u8]) -> Result<Self, BadIcon> {
+        let icon = decoding::decode_png(bytes)?;
+        Self::from_rgba(icon.rgba, icon.width, icon.height)
+    }
+
+    /// Creates an icon from the bytes of an ICO file,
+    /// with all the resolutions it contains, see [`Icon::add_rgba`].
+    #[cfg(feature = "image-ico")]
+    pub fn from_ico_bytes(bytes: &[u8]) -> Result<Self, BadIcon> {
+        Self::from_rgba_icons(decoding::decode_ico(bytes)?)
+    }
+
+    /// Creates an icon from multiple resolutions, the largest one first.
+    #[cfg(feature = "image-ico")]
+    fn from_rgba_icons(mut icons: Vec<RgbaIcon>) -> Result<Self, BadIcon> {
+        icons.sort_by_key(|i| std::cmp::Reverse(i.width * i.height));
+        let mut icons = icons.into_iter();
+        let largest = icons.next().ok_or(BadIcon::UnsupportedFormat)?;
+        let mut icon = Self::from_rgba(largest.rgba, largest.width, largest.height)?;
+        for i in icons {
+            icon.add_rgba(i.rgba, i.width, i.height)?;
+        }
+        Ok(icon)
+    }
+
+    /// Sets the variant of this icon used when a dark theme is in use, or removes it.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Linux (gtk4)**: The variant is picked when the icon is set, based on the
+    ///   `gtk-application-prefer-dark-theme` setting.
+    /// - **Windows / macOS**: Unsupported.
+    pub fn set_dark_variant(&mut self, dark: Option<Icon>) {
+        self.dark = dark.map(|i| i.inner);
+    }
+
+    /// Marks this icon as a template (or symbolic) icon, only its alpha channel is then
+    /// used and it is recolored to match the theme's text color.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Linux (gtk4)**: Recolored to black or white, based on the
+    ///   `gtk-application-prefer-dark-theme` setting.
+    /// - **Windows**: Unsupported.
+    pub fn set_template(&mut self, is_template: bool) {
+        self.is_template = is_template;
+    }
+
+    /// Returns whether this icon is a template icon, see [`Icon::set_template`].
+    pub fn is_template(&self) -> bool {
+        self.is_template
+    }
+
+    /// Create an icon from the path of a PNG or ICO file, with the `image-png`
+    /// and `image-ico` feature flags respectively.
+    ///
+    /// Specify `size` to only load the image of the ICO file closest to that size, or `None`
+    /// to load all of them. It is ignored for PNG files.
+    #[cfg(all(not(windows), feature = "image-png"))]
+    pub fn from_path<P: AsRef<std::path::Path>>(
+        path: P,
+        size: Option<(u32, u32)>,
+    ) -> Result<Self, BadIcon> {
+        let bytes = std::fs::read(path).map_err(BadIcon::OsError)?;
+
+        if bytes.starts_with(decoding::PNG_SIGNATURE) {
+            return Self::from_png_bytes(&bytes);
+        }
+
+        #[cfg(feature = "image-ico")]
+        if bytes.starts_with(&[0, 0, 1, 0]) {
+            let icons = decoding::decode_ico(&bytes)?;
+            return match size {
+                Some((w, h)) => {
+                    let closest = icons
+                        .into_iter()
+                        .min_by_key(|i| {
+                            (
+                                i.width.abs_diff(w) + i.height.abs_diff(h),
+                                u32::MAX - i.width,
+                            )
+                        })
+                        .ok_or(BadIcon::UnsupportedFormat)?;
+                    Self::from_rgba(closest.rgba, closest.width, closest.height)
+                }
+                None => Self::from_rgba_icons(icons),
+            };
+        }
+
+        #[cfg(not(feature = "image-ico"))]
+        let _ = size;
+
+        Err(BadIcon::UnsupportedFormat)
     }
 
     /// Create an icon from a file path.
@@ -148,7 +539,7 @@ impl Icon {
         size: Option<(u32, u32)>,
     ) -> Result<Self, BadIcon> {
         let win_icon = PlatformIcon::from_path(path, size)?;
-        Ok(Icon { inner: win_icon })
+        Ok(Self::from_platform_icon(win_icon))
     }
 
     /// Create an icon from a resource embedded in this executable or library.
@@ -161,7 +552,7 @@ impl Icon {
     #[cfg(windows)]
     pub fn from_resource(ordinal: u16, size: Option<(u32, u32)>) -> Result<Self, BadIcon> {
         let win_icon = PlatformIcon::from_resource(ordinal, size)?;
-        Ok(Icon { inner: win_icon })
+        Ok(Self::from_platform_icon(win_icon))
     }
 }
 
@@ -169,7 +560,8 @@ impl Icon {
 ///
 /// ## Platform-specific:
 ///
-/// - **Windows / Linux**: Unsupported.
+/// - **Windows**: Unsupported.
+/// - **Linux**: Mapped to a freedesktop icon name, see [`NativeIcon::to_freedesktop_name`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NativeIcon {
     /// An add item template image.
@@ -285,3 +677,174 @@ pub enum NativeIcon {
     /// Permissions for guests.
     UserGuest,
 }
+
+impl NativeIcon {
+    /// Returns the name of the closest matching icon from the
+    /// [freedesktop icon naming specification](https://specifications.freedesktop.org/icon-naming-spec/latest/).
+    ///
+    /// This is the icon used for this native icon on Linux.
+    pub fn to_freedesktop_name(self) -> &'static str {
+        match self {
+            NativeIcon::Add => "list-add",
+            NativeIcon::Advanced => "preferences-other",
+            NativeIcon::Bluetooth => "bluetooth",
+            NativeIcon::Bookmarks => "user-bookmarks",
+            NativeIcon::Caution => "dialog-warning",
+            NativeIcon::ColorPanel => "preferences-color",
+            NativeIcon::ColumnView => "view-column",
+            NativeIcon::Computer => "computer",
+            NativeIcon::EnterFullScreen => "view-fullscreen",
+            NativeIcon::Everyone => "system-users",
+            NativeIcon::ExitFullScreen => "view-restore",
+            NativeIcon::FlowView => "view-paged",
+            NativeIcon::Folder => "folder",
+            NativeIcon::FolderBurnable => "media-optical",
+            NativeIcon::FolderSmart => "folder-saved-search",
+            NativeIcon::FollowLinkFreestanding => "go-jump",
+            NativeIcon::FontPanel => "preferences-desktop-font",
+            NativeIcon::GoLeft => "go-previous",
+            NativeIcon::GoRight => "go-next",
+            NativeIcon::Home => "go-home",
+            NativeIcon::IChatTheater => "video-display",
+            NativeIcon::IconView => "view-grid",
+            NativeIcon::Info => "dialog-information",
+            NativeIcon::InvalidDataFreestanding => "dialog-error",
+            NativeIcon::LeftFacingTriangle => "pan-start",
+            NativeIcon::ListView => "view-list",
+            NativeIcon::LockLocked => "changes-prevent",
+            NativeIcon::LockUnlocked => "changes-allow",
+            NativeIcon::MenuMixedState => "list-remove",
+            NativeIcon::MenuOnState => "object-select",
+            NativeIcon::MobileMe => "folder-remote",
+            NativeIcon::MultipleDocuments => "edit-copy",
+            NativeIcon::Network => "network-workgroup",
+            NativeIcon::Path => "folder-open",
+            NativeIcon::PreferencesGeneral => "preferences-system",
+            NativeIcon::QuickLook => "document-print-preview",
+            NativeIcon::RefreshFreestanding => "view-refresh",
+            NativeIcon::Refresh => "view-refresh",
+            NativeIcon::Remove => "list-remove",
+            NativeIcon::RevealFreestanding => "edit-find",
+            NativeIcon::RightFacingTriangle => "pan-end",
+            NativeIcon::Share => "send-to",
+            NativeIcon::Slideshow => "x-office-presentation",
+            NativeIcon::SmartBadge => "emblem-system",
+            NativeIcon::StatusAvailable => "user-available",
+            NativeIcon::StatusNone => "user-offline",
+            NativeIcon::StatusPartiallyAvailable => "user-away",
+            NativeIcon::StatusUnavailable => "user-busy",
+            NativeIcon::StopProgressFreestanding => "process-stop",
+            NativeIcon::StopProgress => "process-stop",
+            NativeIcon::TrashEmpty => "user-trash",
+            NativeIcon::TrashFull => "user-trash-full",
+            NativeIcon::User => "avatar-default",
+            NativeIcon::UserAccounts => "preferences-desktop-personal",
+            NativeIcon::UserGroup => "system-users",
+            NativeIcon::UserGuest => "user-info",
+        }
+    }
+}
+
+#[test]
+fn test_native_icon_freedesktop_name() {
+    assert_eq!(NativeIcon::Add.to_freedesktop_name(), "list-add");
+    assert_eq!(NativeIcon::GoLeft.to_freedesktop_name(), "go-previous");
+    assert_eq!(
+        NativeIcon::TrashFull.to_freedesktop_name(),
+        "user-trash-full"
+    );
+}
+
+#[cfg(feature = "image-png")]
+#[cfg(test)]
+fn encode_png(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .unwrap()
+        .write_image_data(rgba)
+        .unwrap();
+    bytes
+}
+
+#[cfg(feature = "image-png")]
+#[test]
+fn test_decode_png() {
+    let rgba = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    let icon = decoding::decode_png(&encode_png(&rgba, 2, 1)).unwrap();
+    assert_eq!(icon, RgbaIcon::from_rgba(rgba, 2, 1).unwrap());
+
+    assert!(matches!(
+        decoding::decode_png(b"not a png"),
+        Err(BadIcon::PngDecodingError(_))
+    ));
+}
+
+#[cfg(feature = "image-ico")]
+#[test]
+fn test_decode_ico() {
+    // a 2x2 32bpp BMP image, stored bottom-up as BGRA, followed by its AND mask
+    let mut bmp = Vec::new();
+    for v in [40u32, 2, 4] {
+        bmp.extend_from_slice(&v.to_le_bytes());
+    }
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&32u16.to_le_bytes());
+    bmp.extend_from_slice(&[0; 24]);
+    bmp.extend_from_slice(&[3, 2, 1, 255, 6, 5, 4, 255]);
+    bmp.extend_from_slice(&[9, 8, 7, 255, 12, 11, 10, 0]);
+    bmp.extend_from_slice(&[0; 8]);
+
+    let png = encode_png(&[0; 4 * 4 * 4], 4, 4);
+
+    let mut ico = vec![0, 0, 1, 0, 2, 0];
+    let mut offset = 6 + 2 * 16;
+    for (size, data) in [(2u8, &bmp), (4, &png)] {
+        ico.extend_from_slice(&[size, size, 0, 0, 1, 0, 32, 0]);
+        ico.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        ico.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += data.len();
+    }
+    ico.extend_from_slice(&bmp);
+    ico.extend_from_slice(&png);
+
+    let icons = decoding::decode_ico(&ico).unwrap();
+    assert_eq!(
+        icons[0],
+        RgbaIcon::from_rgba(
+            vec![7, 8, 9, 255, 10, 11, 12, 0, 1, 2, 3, 255, 4, 5, 6, 255],
+            2,
+            2
+        )
+        .unwrap()
+    );
+    assert_eq!((icons[1].width, icons[1].height), (4, 4));
+
+    assert!(matches!(
+        decoding::decode_ico(&[0, 0, 1, 0, 1, 0]),
+        Err(BadIcon::IcoDecodingError(_))
+    ));
+}
+
+#[cfg(feature = "svg")]
+#[test]
+fn test_svg_icon() {
+    let svg = SvgIcon::from_bytes(
+        br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10" fill="red"/></svg>"#,
+    )
+    .unwrap();
+    assert_eq!(svg.size(), (10, 10));
+
+    let icon = svg.rasterize(32, 32);
+    assert_eq!((icon.width, icon.height), (32, 32));
+    assert_eq!(&icon.rgba[..4], &[255, 0, 0, 255]);
+    assert_eq!(svg.cache.lock().unwrap().len(), 1);
+
+    assert!(matches!(
+        SvgIcon::from_bytes(b"not an svg"),
+        Err(BadIcon::SvgDecodingError(_))
+    ));
+}
@@ -4,7 +4,11 @@
 
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{accelerator::Accelerator, IsMenuItem, MenuItemKind};
+use crate::{
+    accelerator::{Accelerator, AcceleratorSequence},
+    icon::Icon,
+    IsMenuItem, MenuItemKind,
+};
 
 /// A check menu item inside a [`Menu`] or [`Submenu`]
 /// and usually contains a text and a check mark or a similar toggle
@@ -66,15 +70,73 @@ impl CheckMenuItem {
 
     /// Enable or disable this check menu item.
     pub fn set_enabled(&self, enabled: bool) {
-        self.0.borrow_mut().set_enabled(enabled)
+        self.0.borrow_mut().set_enabled(enabled);
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::set_enabled(self.id(), enabled);
+    }
+
+    /// Get this check menu item accelerator.
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.0.borrow().accelerator()
     }
 
     /// Set this check menu item accelerator.
     pub fn set_accelerator(&self, acccelerator: Option<Accelerator>) -> crate::Result<()> {
-        self.0.borrow_mut().set_accelerator(acccelerator)
+        self.kind()
+            .set_accelerators_with(acccelerator.as_slice(), |child| {
+                child.set_accelerator(acccelerator)
+            })
+    }
+
+    /// Get all the accelerators of this check menu item, the first one being the displayed [`Self::accelerator`].
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.0.borrow().accelerators()
+    }
+
+    /// Set several accelerators for this check menu item, see [`MenuItem::set_accelerators`](crate::MenuItem::set_accelerators).
+    pub fn set_accelerators<I: IntoIterator<Item = Accelerator>>(
+        &self,
+        accelerators: I,
+    ) -> crate::Result<()> {
+        let accelerators = accelerators.into_iter().collect::<Vec<_>>();
+        self.kind().set_accelerators_with(&accelerators, |child| {
+            child.set_accelerators(accelerators.clone())
+        })
+    }
+
+    /// Set this check menu item accelerator to a sequence of accelerators, see [`MenuItem::set_accelerator_sequence`](crate::MenuItem::set_accelerator_sequence).
+    pub fn set_accelerator_sequence(
+        &self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        let accelerators = sequence
+            .as_ref()
+            .map(|s| s.accelerators())
+            .filter(|a| a.len() == 1)
+            .unwrap_or_default();
+        self.kind().set_accelerators_with(accelerators, |child| {
+            child.set_accelerator_sequence(sequence.clone())
+        })
+    }
+
+    /// Registers the accelerators of this check menu item as global hotkeys or unregisters them, see [`MenuItem::set_global_hotkey`](crate::MenuItem::set_global_hotkey).
+    ///
+    /// Unlike activating the item from its menu, the hotkeys don't toggle its check state,
+    /// use [`CheckMenuItem::set_checked`] when handling their event.
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    pub fn set_global_hotkey(&self, global: bool) -> crate::Result<()> {
+        crate::platform_impl::x11_hotkeys::set_global_hotkey(self, global)
+    }
+
+    /// Returns whether the accelerators of this check menu item are registered as global hotkeys.
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    pub fn is_global_hotkey(&self) -> bool {
+        crate::platform_impl::x11_hotkeys::is_registered(self.id())
     }
 
     /// Get whether this check menu item is checked or not.
+    ///
+    /// Returns `false` when the item is in the [`CheckState::Mixed`] state.
     pub fn is_checked(&self) -> bool {
         self.0.borrow().is_checked()
     }
@@ -83,4 +145,75 @@ impl CheckMenuItem {
     pub fn set_checked(&self, checked: bool) {
         self.0.borrow_mut().set_checked(checked)
     }
+
+    /// Get the state of this check menu item.
+    pub fn state(&self) -> CheckState {
+        let item = self.0.borrow();
+        if item.is_mixed() {
+            CheckState::Mixed
+        } else if item.is_checked() {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
+
+    /// Set the state of this check menu item. Activating an item in the
+    /// [`CheckState::Mixed`] state checks it.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / Linux (gtk4)**: [`CheckState::Mixed`] is shown as unchecked.
+    /// - **Linux (gtk3)**: [`CheckState::Mixed`] is shown as unchecked in exported `gio::MenuModel`s.
+    pub fn set_state(&self, state: CheckState) {
+        let mut item = self.0.borrow_mut();
+        item.set_checked(state == CheckState::Checked);
+        if state == CheckState::Mixed {
+            item.set_mixed(true);
+        }
+    }
+
+    /// Change this check menu item icon or remove it.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Unsupported.
+    pub fn set_icon(&self, _icon: Option<Icon>) {
+        #[cfg(not(windows))]
+        self.0.borrow_mut().set_icon(_icon)
+    }
+}
+
+/// The state of a [`CheckMenuItem`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CheckState {
+    #[default]
+    Unchecked,
+    Checked,
+    /// An indeterminate state, usually reflecting a setting that only
+    /// applies to some of the selected elements.
+    Mixed,
+}
+
+#[test]
+fn test_check_state() {
+    let item = CheckMenuItem::new("Wrap", true, false, None);
+    assert_eq!(item.state(), CheckState::Unchecked);
+
+    item.set_state(CheckState::Mixed);
+    assert_eq!(item.state(), CheckState::Mixed);
+    assert!(!item.is_checked());
+
+    item.set_state(CheckState::Checked);
+    assert_eq!(item.state(), CheckState::Checked);
+    assert!(item.is_checked());
+
+    item.set_state(CheckState::Mixed);
+    item.set_checked(false);
+    assert_eq!(item.state(), CheckState::Unchecked);
+
+    item.set_state(CheckState::Mixed);
+    let event = crate::menu::activate_item(item.kind());
+    assert_eq!(event.id, item.id());
+    assert_eq!(item.state(), CheckState::Checked);
 }
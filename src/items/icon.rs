@@ -5,7 +5,7 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, AcceleratorSequence},
     icon::{Icon, NativeIcon},
     IsMenuItem, MenuItemKind,
 };
@@ -46,7 +46,8 @@ impl IconMenuItem {
     ///
     /// ## Platform-specific:
     ///
-    /// - **Windows / Linux**: Unsupported.
+    /// - **Windows**: Unsupported.
+    /// - **Linux**: Uses the icon from the current icon theme named by [`NativeIcon::to_freedesktop_name`].
     pub fn with_native_icon<S: AsRef<str>>(
         text: S,
         enabled: bool,
@@ -63,6 +64,26 @@ impl IconMenuItem {
         )))
     }
 
+    /// Create a new icon menu item but with an icon from the current icon theme,
+    /// `icon_name` being a [freedesktop icon name](https://specifications.freedesktop.org/icon-naming-spec/latest/)
+    /// like `"document-save"`.
+    ///
+    /// See [`IconMenuItem::new`] for more info.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / macOS**: Unsupported.
+    pub fn with_icon_name<S: AsRef<str>>(
+        text: S,
+        enabled: bool,
+        icon_name: Option<&str>,
+        acccelerator: Option<Accelerator>,
+    ) -> Self {
+        let item = Self::new(text, enabled, None, acccelerator);
+        item.set_icon_name(icon_name);
+        item
+    }
+
     /// Returns a unique identifier associated with this submenu.
     pub fn id(&self) -> u32 {
         self.0.borrow().id()
@@ -87,12 +108,65 @@ impl IconMenuItem {
 
     /// Enable or disable this check menu item.
     pub fn set_enabled(&self, enabled: bool) {
-        self.0.borrow_mut().set_enabled(enabled)
+        self.0.borrow_mut().set_enabled(enabled);
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::set_enabled(self.id(), enabled);
+    }
+
+    /// Get this icon menu item accelerator.
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.0.borrow().accelerator()
     }
 
     /// Set this icon menu item accelerator.
     pub fn set_accelerator(&self, acccelerator: Option<Accelerator>) -> crate::Result<()> {
-        self.0.borrow_mut().set_accelerator(acccelerator)
+        self.kind()
+            .set_accelerators_with(acccelerator.as_slice(), |child| {
+                child.set_accelerator(acccelerator)
+            })
+    }
+
+    /// Get all the accelerators of this icon menu item, the first one being the displayed [`Self::accelerator`].
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.0.borrow().accelerators()
+    }
+
+    /// Set several accelerators for this icon menu item, see [`MenuItem::set_accelerators`](crate::MenuItem::set_accelerators).
+    pub fn set_accelerators<I: IntoIterator<Item = Accelerator>>(
+        &self,
+        accelerators: I,
+    ) -> crate::Result<()> {
+        let accelerators = accelerators.into_iter().collect::<Vec<_>>();
+        self.kind().set_accelerators_with(&accelerators, |child| {
+            child.set_accelerators(accelerators.clone())
+        })
+    }
+
+    /// Set this icon menu item accelerator to a sequence of accelerators, see [`MenuItem::set_accelerator_sequence`](crate::MenuItem::set_accelerator_sequence).
+    pub fn set_accelerator_sequence(
+        &self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        let accelerators = sequence
+            .as_ref()
+            .map(|s| s.accelerators())
+            .filter(|a| a.len() == 1)
+            .unwrap_or_default();
+        self.kind().set_accelerators_with(accelerators, |child| {
+            child.set_accelerator_sequence(sequence.clone())
+        })
+    }
+
+    /// Registers the accelerators of this icon menu item as global hotkeys or unregisters them, see [`MenuItem::set_global_hotkey`](crate::MenuItem::set_global_hotkey).
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    pub fn set_global_hotkey(&self, global: bool) -> crate::Result<()> {
+        crate::platform_impl::x11_hotkeys::set_global_hotkey(self, global)
+    }
+
+    /// Returns whether the accelerators of this icon menu item are registered as global hotkeys.
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    pub fn is_global_hotkey(&self) -> bool {
+        crate::platform_impl::x11_hotkeys::is_registered(self.id())
     }
 
     /// Change this menu item icon or remove it.
@@ -104,9 +178,24 @@ impl IconMenuItem {
     ///
     /// ## Platform-specific:
     ///
-    /// - **Windows / Linux**: Unsupported.
+    /// - **Windows**: Unsupported.
+    /// - **Linux**: Uses the icon from the current icon theme named by [`NativeIcon::to_freedesktop_name`].
     pub fn set_native_icon(&mut self, _icon: Option<NativeIcon>) {
         #[cfg(target_os = "macos")]
-        self.0.borrow_mut().set_native_icon(_icon)
+        self.0.borrow_mut().set_native_icon(_icon);
+        #[cfg(target_os = "linux")]
+        self.set_icon_name(_icon.map(NativeIcon::to_freedesktop_name));
+    }
+
+    /// Change this menu item icon to an icon from the current icon theme or remove it.
+    ///
+    /// See [`IconMenuItem::with_icon_name`] for more info.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows / macOS**: Unsupported.
+    pub fn set_icon_name(&self, _icon_name: Option<&str>) {
+        #[cfg(target_os = "linux")]
+        self.0.borrow_mut().set_icon_name(_icon_name)
     }
 }
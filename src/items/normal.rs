@@ -1,6 +1,9 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{accelerator::Accelerator, IsMenuItem, MenuItemKind};
+use crate::{
+    accelerator::{Accelerator, AcceleratorSequence},
+    IsMenuItem, MenuItemKind,
+};
 
 /// A menu item inside a [`Menu`] or [`Submenu`] and contains only text.
 ///
@@ -52,11 +55,117 @@ impl MenuItem {
 
     /// Enable or disable this menu item.
     pub fn set_enabled(&self, enabled: bool) {
-        self.0.borrow_mut().set_enabled(enabled)
+        self.0.borrow_mut().set_enabled(enabled);
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::set_enabled(self.id(), enabled);
+    }
+
+    /// Get this menu item accelerator.
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.0.borrow().accelerator()
     }
 
     /// Set this menu item accelerator.
     pub fn set_accelerator(&self, acccelerator: Option<Accelerator>) -> crate::Result<()> {
-        self.0.borrow_mut().set_accelerator(acccelerator)
+        self.kind()
+            .set_accelerators_with(acccelerator.as_slice(), |child| {
+                child.set_accelerator(acccelerator)
+            })
+    }
+
+    /// Get all the accelerators of this menu item, the first one being the displayed [`Self::accelerator`].
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.0.borrow().accelerators()
+    }
+
+    /// Set several accelerators for this menu item, for example `Ctrl+Y` and `Ctrl+Shift+Z` for redo.
+    ///
+    /// All of them trigger this menu item but only the first one is displayed.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **macOS**: Only the first accelerator triggers this menu item.
+    pub fn set_accelerators<I: IntoIterator<Item = Accelerator>>(
+        &self,
+        accelerators: I,
+    ) -> crate::Result<()> {
+        let accelerators = accelerators.into_iter().collect::<Vec<_>>();
+        self.kind().set_accelerators_with(&accelerators, |child| {
+            child.set_accelerators(accelerators.clone())
+        })
+    }
+
+    /// Set this menu item accelerator to a sequence of accelerators, for example `Ctrl+K Ctrl+S`.
+    ///
+    /// Menus can't trigger multi-key sequences themselves, handle them with an
+    /// [`AcceleratorSequenceMatcher`](crate::accelerator::AcceleratorSequenceMatcher) instead.
+    /// A sequence of a single accelerator is the same as [`Self::set_accelerator`].
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **macOS / Linux (gtk4)**: Multi-key sequences are not displayed.
+    pub fn set_accelerator_sequence(
+        &self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        let accelerators = sequence
+            .as_ref()
+            .map(|s| s.accelerators())
+            .filter(|a| a.len() == 1)
+            .unwrap_or_default();
+        self.kind().set_accelerators_with(accelerators, |child| {
+            child.set_accelerator_sequence(sequence.clone())
+        })
+    }
+
+    /// Registers the accelerators of this menu item as global hotkeys, emitting a
+    /// [`MenuEvent`](crate::MenuEvent) for this item even when no window of the application
+    /// is focused, or unregisters them.
+    ///
+    /// The hotkeys follow later accelerator changes, don't emit events while this item
+    /// is disabled and are unregistered when this item is removed from the last menu or submenu
+    /// it is in or dropped. Returns [`crate::Error::GlobalHotKeyAlreadyGrabbed`]
+    /// if another application already uses one of them.
+    ///
+    /// Requires the `x11-global-hotkeys` feature and an X11 session,
+    /// Wayland doesn't let applications grab keys.
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    pub fn set_global_hotkey(&self, global: bool) -> crate::Result<()> {
+        crate::platform_impl::x11_hotkeys::set_global_hotkey(self, global)
+    }
+
+    /// Returns whether the accelerators of this menu item are registered as global hotkeys.
+    #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+    pub fn is_global_hotkey(&self) -> bool {
+        crate::platform_impl::x11_hotkeys::is_registered(self.id())
+    }
+}
+
+#[test]
+fn test_accelerators() {
+    let redo = "Ctrl+KeyY".parse::<Accelerator>().unwrap();
+    let alternate = "Ctrl+Shift+KeyZ".parse::<Accelerator>().unwrap();
+
+    let item = MenuItem::new("Redo", true, None);
+    assert!(item.accelerators().is_empty());
+
+    item.set_accelerators([redo, alternate]).unwrap();
+    assert_eq!(item.accelerator(), Some(redo));
+    assert_eq!(item.accelerators(), [redo, alternate]);
+
+    item.set_accelerator(Some(alternate)).unwrap();
+    assert_eq!(item.accelerators(), [alternate]);
+    item.set_accelerator(None).unwrap();
+    assert!(item.accelerators().is_empty());
+
+    // an unsupported accelerator doesn't replace the current ones
+    #[cfg(windows)]
+    {
+        use crate::accelerator::{Code, Modifiers};
+
+        item.set_accelerators([redo, alternate]).unwrap();
+        let hyper = Accelerator::new(None, Code::KeyA).with_modifiers(Modifiers::HYPER);
+        assert!(item.set_accelerators([alternate, hyper]).is_err());
+        assert_eq!(item.accelerators(), [redo, alternate]);
     }
 }
@@ -4,7 +4,7 @@
 
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{util::AddOp, ContextMenu, IsMenuItem, MenuItemKind, Position};
+use crate::{icon::Icon, util::AddOp, ContextMenu, IsMenuItem, MenuItemKind, Position};
 
 /// A menu that can be added to a [`Menu`] or another [`Submenu`].
 ///
@@ -47,7 +47,11 @@ impl Submenu {
 
     /// Add a menu item to the end of this menu.
     pub fn append(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.0.borrow_mut().add_menu_item(item, AddOp::Append)
+        crate::menu::check_strict_accelerators_for_item(self.id(), item)?;
+        self.0.borrow_mut().add_menu_item(item, AddOp::Append)?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_added(item, Some(self));
+        Ok(())
     }
 
     /// Add menu items to the end of this submenu. It calls [`Submenu::append`] in a loop.
@@ -61,7 +65,11 @@ impl Submenu {
 
     /// Add a menu item to the beginning of this submenu.
     pub fn prepend(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.0.borrow_mut().add_menu_item(item, AddOp::Insert(0))
+        crate::menu::check_strict_accelerators_for_item(self.id(), item)?;
+        self.0.borrow_mut().add_menu_item(item, AddOp::Insert(0))?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_added(item, Some(self));
+        Ok(())
     }
 
     /// Add menu items to the beginning of this submenu.
@@ -73,9 +81,13 @@ impl Submenu {
 
     /// Insert a menu item at the specified `postion` in the submenu.
     pub fn insert(&self, item: &dyn IsMenuItem, position: usize) -> crate::Result<()> {
+        crate::menu::check_strict_accelerators_for_item(self.id(), item)?;
         self.0
             .borrow_mut()
-            .add_menu_item(item, AddOp::Insert(position))
+            .add_menu_item(item, AddOp::Insert(position))?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_added(item, Some(self));
+        Ok(())
     }
 
     /// Insert menu items at the specified `postion` in the submenu.
@@ -89,7 +101,10 @@ impl Submenu {
 
     /// Remove a menu item from this submenu.
     pub fn remove(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.0.borrow_mut().remove(item)
+        self.0.borrow_mut().remove(item)?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_removed(item, Some(self));
+        Ok(())
     }
 
     /// Returns a list of menu items that has been added to this submenu.
@@ -119,6 +134,35 @@ impl Submenu {
         self.0.borrow_mut().set_enabled(enabled)
     }
 
+    /// Change this submenu icon or remove it.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Unsupported.
+    pub fn set_icon(&self, _icon: Option<Icon>) {
+        #[cfg(not(windows))]
+        self.0.borrow_mut().set_icon(_icon)
+    }
+
+    /// Returns a [`gio::MenuModel`](gtk::gio::MenuModel) mirroring this submenu, whose items activate
+    /// [`gio::SimpleAction`](gtk::gio::SimpleAction)s registered on `app` under the `app.` prefix.
+    ///
+    /// See [`Menu::gtk_menu_model_for_gtk_application`](crate::Menu::gtk_menu_model_for_gtk_application)
+    /// for more info.
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
+    pub fn gtk_menu_model_for_gtk_application<A>(
+        &self,
+        app: &A,
+    ) -> crate::Result<gtk::gio::MenuModel>
+    where
+        A: gtk::prelude::IsA<gtk::Application>,
+    {
+        use gtk::prelude::Cast;
+        self.0
+            .borrow_mut()
+            .gtk_menu_model_for_gtk_application(app.upcast_ref())
+    }
+
     /// Set this submenu as the Window menu for the application on macOS.
     ///
     /// This will cause macOS to automatically add window-switching items and
@@ -163,7 +207,7 @@ impl ContextMenu for Submenu {
         self.0.borrow_mut().detach_menu_subclass_from_hwnd(hwnd)
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     fn show_context_menu_for_gtk_window(
         &self,
         w: &gtk::ApplicationWindow,
@@ -174,11 +218,27 @@ impl ContextMenu for Submenu {
             .show_context_menu_for_gtk_window(w, position)
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     fn gtk_context_menu(&self) -> gtk::Menu {
         self.0.borrow_mut().gtk_context_menu()
     }
 
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    fn show_context_menu_for_gtk_window(
+        &self,
+        w: &gtk4::ApplicationWindow,
+        position: Option<Position>,
+    ) {
+        self.0
+            .borrow_mut()
+            .show_context_menu_for_gtk_window(w, position)
+    }
+
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    fn gtk_context_menu(&self) -> gtk4::PopoverMenu {
+        self.0.borrow_mut().gtk_context_menu()
+    }
+
     #[cfg(target_os = "macos")]
     fn show_context_menu_for_nsview(&self, view: cocoa::base::id, position: Option<Position>) {
         self.0
@@ -0,0 +1,282 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Keymaps let users rebind the accelerators of menu items at runtime.
+//!
+//! A [`Keymap`] maps stable ids, chosen by the application, to accelerator strings
+//! as parsed by [`Accelerator::from_str`](crate::accelerator::Accelerator#impl-FromStr-for-Accelerator).
+//! An empty accelerator string removes the accelerator of an item.
+//!
+//! Keymaps can be loaded from a simple text format with one `id = accelerator`
+//! binding per line, where lines starting with `#` are comments:
+//!
+//! ```no_run
+//! # use muda::{keymap::Keymap, MenuItem, IsMenuItem};
+//! let save = MenuItem::new("Save", true, None);
+//! let close = MenuItem::new("Close", true, None);
+//!
+//! let keymap: Keymap = r#"
+//! # my bindings
+//! file.save = CmdOrCtrl+Shift+KeyS
+//! file.close =
+//! "#
+//! .parse()
+//! .unwrap();
+//!
+//! let report = keymap.apply([
+//!     ("file.save", &save as &dyn IsMenuItem),
+//!     ("file.close", &close as &dyn IsMenuItem),
+//! ]);
+//! assert!(report.is_ok());
+//! ```
+//!
+//! With the `serde` feature, a [`Keymap`] is also (de)serialized as a map from ids to accelerator strings.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::{accelerator::Accelerator, IsMenuItem, MenuItemKind};
+
+/// A set of accelerator bindings keyed by stable item ids.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Keymap {
+    bindings: BTreeMap<String, String>,
+}
+
+impl Keymap {
+    /// Creates an empty keymap.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Binds the item with `id` to `accelerator`, replacing any previous binding.
+    ///
+    /// `accelerator` is only parsed when the keymap is applied, an empty string removes the accelerator.
+    pub fn insert<I: Into<String>, A: Into<String>>(&mut self, id: I, accelerator: A) {
+        self.bindings.insert(id.into(), accelerator.into());
+    }
+
+    /// Removes the binding of the item with `id`.
+    pub fn remove(&mut self, id: &str) -> Option<String> {
+        self.bindings.remove(id)
+    }
+
+    /// Returns the accelerator string bound to the item with `id`.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.bindings.get(id).map(String::as_str)
+    }
+
+    /// Returns an iterator over the ids and accelerator strings of this keymap, sorted by id.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.bindings
+            .iter()
+            .map(|(id, accelerator)| (id.as_str(), accelerator.as_str()))
+    }
+
+    /// Applies this keymap to `items`, pairs of stable ids and menu items,
+    /// by calling `set_accelerator` on every item that has a binding.
+    ///
+    /// Items without a binding keep their accelerator. Bindings that fail to apply
+    /// leave the item unchanged and are reported in the returned [`KeymapReport`],
+    /// along with the accelerators shared by more than one of `items` afterwards.
+    pub fn apply<'a, I>(&self, items: I) -> KeymapReport
+    where
+        I: IntoIterator<Item = (&'a str, &'a dyn IsMenuItem)>,
+    {
+        let items = items
+            .into_iter()
+            .map(|(id, item)| (id, item.kind()))
+            .collect::<Vec<_>>();
+
+        let mut report = KeymapReport::default();
+
+        for (id, accelerator) in &self.bindings {
+            let Some((_, item)) = items.iter().find(|(i, _)| i == id) else {
+                report.unknown_ids.push(id.clone());
+                continue;
+            };
+
+            let result = parse_binding(accelerator).and_then(|a| set_accelerator(item, a));
+            if let Err(e) = result {
+                report.errors.push((id.clone(), e));
+            }
+        }
+
+        for (id, item) in &items {
+            let Some(accelerator) = accelerator(item) else {
+                continue;
+            };
+            match report.conflicts.iter_mut().find(|(a, _)| *a == accelerator) {
+                Some((_, ids)) => ids.push(id.to_string()),
+                None => report.conflicts.push((accelerator, vec![id.to_string()])),
+            }
+        }
+        report.conflicts.retain(|(_, ids)| ids.len() > 1);
+
+        report
+    }
+}
+
+impl FromStr for Keymap {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut keymap = Keymap::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_once('=') {
+                Some((id, accelerator)) if !id.trim().is_empty() => {
+                    keymap.insert(id.trim(), accelerator.trim())
+                }
+                _ => return Err(crate::Error::KeymapSyntaxError(i + 1, line.to_string())),
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+impl<I: Into<String>, A: Into<String>> FromIterator<(I, A)> for Keymap {
+    fn from_iter<T: IntoIterator<Item = (I, A)>>(iter: T) -> Self {
+        let mut keymap = Keymap::new();
+        for (id, accelerator) in iter {
+            keymap.insert(id, accelerator);
+        }
+        keymap
+    }
+}
+
+/// The outcome of [`Keymap::apply`].
+#[derive(Debug, Default)]
+pub struct KeymapReport {
+    /// Ids bound in the keymap that don't belong to any of the items.
+    pub unknown_ids: Vec<String>,
+    /// Bindings that couldn't be applied, because the accelerator couldn't
+    /// be parsed or isn't supported by the item or platform.
+    pub errors: Vec<(String, crate::Error)>,
+    /// Accelerators shared by more than one item, with the ids of these items.
+    pub conflicts: Vec<(Accelerator, Vec<String>)>,
+}
+
+impl KeymapReport {
+    /// Returns `true` if every binding was applied and no accelerator is shared.
+    pub fn is_ok(&self) -> bool {
+        self.unknown_ids.is_empty() && self.errors.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+fn parse_binding(accelerator: &str) -> crate::Result<Option<Accelerator>> {
+    if accelerator.trim().is_empty() {
+        Ok(None)
+    } else {
+        accelerator.parse().map(Some)
+    }
+}
+
+fn set_accelerator(item: &MenuItemKind, accelerator: Option<Accelerator>) -> crate::Result<()> {
+    match item {
+        MenuItemKind::MenuItem(i) => i.set_accelerator(accelerator),
+        MenuItemKind::Check(i) => i.set_accelerator(accelerator),
+        MenuItemKind::Icon(i) => i.set_accelerator(accelerator),
+        MenuItemKind::Submenu(_) | MenuItemKind::Predefined(_) => {
+            Err(crate::Error::AcceleratorNotSupported)
+        }
+    }
+}
+
+fn accelerator(item: &MenuItemKind) -> Option<Accelerator> {
+    match item {
+        MenuItemKind::MenuItem(i) => i.accelerator(),
+        MenuItemKind::Check(i) => i.accelerator(),
+        MenuItemKind::Icon(i) => i.accelerator(),
+        MenuItemKind::Submenu(_) | MenuItemKind::Predefined(_) => None,
+    }
+}
+
+#[test]
+fn test_parse_keymap() {
+    let keymap: Keymap = "
+        # comment
+        file.save = CmdOrCtrl+Shift+KeyS
+
+        file.close=
+        view.zoom_in = Ctrl+=
+    "
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        keymap.iter().collect::<Vec<_>>(),
+        [
+            ("file.close", ""),
+            ("file.save", "CmdOrCtrl+Shift+KeyS"),
+            ("view.zoom_in", "Ctrl+=")
+        ]
+    );
+
+    assert!(matches!(
+        "file.save = Ctrl+KeyS\nfile.close".parse::<Keymap>(),
+        Err(crate::Error::KeymapSyntaxError(2, _))
+    ));
+    assert!("= Ctrl+KeyS".parse::<Keymap>().is_err());
+    assert_eq!(parse_binding("").unwrap(), None);
+    assert!(parse_binding("Ctrl+Foo").is_err());
+}
+
+#[test]
+fn test_apply_keymap() {
+    use crate::{MenuItem, Submenu};
+
+    let save = MenuItem::new("Save", true, "Ctrl+KeyS".parse().ok());
+    let open = MenuItem::new("Open", true, None);
+    let close = MenuItem::new("Close", true, "Ctrl+KeyW".parse().ok());
+    let quit = MenuItem::new("Quit", true, "Ctrl+KeyQ".parse().ok());
+    let edit = Submenu::new("Edit", true);
+
+    let keymap: Keymap = [
+        ("file.save", "Ctrl+Shift+KeyS"),
+        ("file.open", "Ctrl+Shift+KeyS"),
+        ("file.close", ""),
+        ("file.quit", "Ctrl+Foo"),
+        ("edit", "Ctrl+KeyE"),
+        ("view.zoom_in", "Ctrl+="),
+    ]
+    .into_iter()
+    .collect();
+    let report = keymap.apply([
+        ("file.save", &save as &dyn IsMenuItem),
+        ("file.open", &open),
+        ("file.close", &close),
+        ("file.quit", &quit),
+        ("edit", &edit),
+    ]);
+
+    assert!(!report.is_ok());
+    assert_eq!(report.unknown_ids, ["view.zoom_in"]);
+    assert!(matches!(
+        &report.errors[..],
+        [
+            (edit, crate::Error::AcceleratorNotSupported),
+            (quit, crate::Error::AcceleratorParseError(_)),
+        ] if edit == "edit" && quit == "file.quit"
+    ));
+    assert_eq!(
+        report.conflicts,
+        [(
+            "Ctrl+Shift+KeyS".parse().unwrap(),
+            vec!["file.save".to_string(), "file.open".to_string()]
+        )]
+    );
+
+    assert_eq!(save.accelerator(), "Ctrl+Shift+KeyS".parse().ok());
+    assert_eq!(close.accelerator(), None);
+    assert_eq!(quit.accelerator(), "Ctrl+KeyQ".parse().ok());
+}
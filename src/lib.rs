@@ -74,14 +74,14 @@
 //! ```no_run
 //! # let menu = muda::Menu::new();
 //! # let window_hwnd = 0;
-//! # #[cfg(target_os = "linux")]
+//! # #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 //! # let gtk_window = gtk::ApplicationWindow::builder().build();
-//! # #[cfg(target_os = "linux")]
+//! # #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 //! # let vertical_gtk_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
 //! // --snip--
 //! #[cfg(target_os = "windows")]
 //! menu.init_for_hwnd(window_hwnd);
-//! #[cfg(target_os = "linux")]
+//! #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 //! menu.init_for_gtk_window(&gtk_window, Some(&vertical_gtk_box));
 //! #[cfg(target_os = "macos")]
 //! menu.init_for_nsapp();
@@ -95,7 +95,7 @@
 //! use muda::ContextMenu;
 //! # let menu = muda::Menu::new();
 //! # let window_hwnd = 0;
-//! # #[cfg(target_os = "linux")]
+//! # #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 //! # let gtk_window = gtk::ApplicationWindow::builder().build();
 //! # #[cfg(target_os = "macos")]
 //! # let nsview = 0 as *mut objc::runtime::Object;
@@ -103,7 +103,7 @@
 //! let position = muda::PhysicalPosition { x: 100., y: 120. };
 //! #[cfg(target_os = "windows")]
 //! menu.show_context_menu_for_hwnd(window_hwnd, Some(position.into()));
-//! #[cfg(target_os = "linux")]
+//! #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 //! menu.show_context_menu_for_gtk_window(&gtk_window, Some(position.into()));
 //! #[cfg(target_os = "macos")]
 //! menu.show_context_menu_for_nsview(nsview, Some(position.into()));
@@ -132,17 +132,36 @@ use once_cell::sync::{Lazy, OnceCell};
 mod about_metadata;
 pub mod accelerator;
 pub mod builders;
+pub mod diff;
 mod dpi;
 mod error;
 mod items;
+pub mod keymap;
 mod menu;
+pub mod palette;
 mod platform_impl;
+#[cfg(feature = "tao")]
+pub mod tao;
 mod util;
+#[cfg(feature = "winit")]
+pub mod winit;
 
 #[cfg(target_os = "macos")]
 #[macro_use]
 extern crate objc;
 
+#[cfg(all(target_os = "linux", not(any(feature = "gtk3", feature = "gtk4"))))]
+compile_error!(
+    "either the `gtk3` or the `gtk4` feature must be enabled on Linux, \
+     add `features = [\"gtk3\"]` to keep the default backend with `default-features = false`"
+);
+
+#[cfg(all(target_os = "linux", feature = "gtk3", feature = "gtk4"))]
+compile_error!(
+    "the `gtk3` and `gtk4` features can't be enabled together, \
+     set `default-features = false` when enabling the `gtk4` feature"
+);
+
 pub use about_metadata::AboutMetadata;
 pub use dpi::*;
 pub use error::*;
@@ -309,7 +328,7 @@ pub trait ContextMenu {
     /// Shows this menu as a context menu inside a [`gtk::ApplicationWindow`]
     ///
     /// - `position` is relative to the window top-left corner, if `None`, the cursor position is used.
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     fn show_context_menu_for_gtk_window(
         &self,
         w: &gtk::ApplicationWindow,
@@ -317,9 +336,23 @@ pub trait ContextMenu {
     );
 
     /// Get the underlying gtk menu reserved for context menus.
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     fn gtk_context_menu(&self) -> gtk::Menu;
 
+    /// Shows this menu as a context menu inside a [`gtk4::ApplicationWindow`]
+    ///
+    /// - `position` is relative to the window top-left corner, if `None`, the cursor position is used.
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    fn show_context_menu_for_gtk_window(
+        &self,
+        w: &gtk4::ApplicationWindow,
+        position: Option<Position>,
+    );
+
+    /// Get the underlying gtk popover menu reserved for context menus.
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    fn gtk_context_menu(&self) -> gtk4::PopoverMenu;
+
     /// Shows this menu as a context menu for the specified `NSView`.
     ///
     /// - `position` is relative to the window top-left corner, if `None`, the cursor position is used.
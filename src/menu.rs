@@ -2,9 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::{cell::RefCell, rc::Rc};
-
-use crate::{util::AddOp, ContextMenu, IsMenuItem, MenuItemKind, Position};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    accelerator::{Accelerator, Code, Key, Modifiers},
+    palette::{CommandPalette, PaletteEntry},
+    util::AddOp,
+    ContextMenu, IsMenuItem, MenuEvent, MenuItemKind, Position,
+};
+
+thread_local! {
+    /// Menus that reject duplicate accelerators, see [`Menu::set_strict_accelerators`].
+    static STRICT_MENUS: RefCell<Vec<Weak<RefCell<crate::platform_impl::Menu>>>> =
+        const { RefCell::new(Vec::new()) };
+}
 
 /// A root menu that can be added to a Window on Windows and Linux
 /// and used as the app global menu on macOS.
@@ -43,7 +57,11 @@ impl Menu {
     ///
     /// [`Submenu`]: crate::Submenu
     pub fn append(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.0.borrow_mut().add_menu_item(item, AddOp::Append)
+        check_strict_accelerators_for_item(self.id(), item)?;
+        self.0.borrow_mut().add_menu_item(item, AddOp::Append)?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_added(item, None);
+        Ok(())
     }
 
     /// Add menu items to the end of this menu. It calls [`Menu::append`] in a loop internally.
@@ -69,7 +87,11 @@ impl Menu {
     ///
     /// [`Submenu`]: crate::Submenu
     pub fn prepend(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.0.borrow_mut().add_menu_item(item, AddOp::Insert(0))
+        check_strict_accelerators_for_item(self.id(), item)?;
+        self.0.borrow_mut().add_menu_item(item, AddOp::Insert(0))?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_added(item, None);
+        Ok(())
     }
 
     /// Add menu items to the beginning of this menu. It calls [`Menu::insert_items`] with position of `0` internally.
@@ -91,9 +113,13 @@ impl Menu {
     ///
     /// [`Submenu`]: crate::Submenu
     pub fn insert(&self, item: &dyn IsMenuItem, position: usize) -> crate::Result<()> {
+        check_strict_accelerators_for_item(self.id(), item)?;
         self.0
             .borrow_mut()
-            .add_menu_item(item, AddOp::Insert(position))
+            .add_menu_item(item, AddOp::Insert(position))?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_added(item, None);
+        Ok(())
     }
 
     /// Insert menu items at the specified `postion` in the menu.
@@ -113,7 +139,10 @@ impl Menu {
 
     /// Remove a menu item from this menu.
     pub fn remove(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.0.borrow_mut().remove(item)
+        self.0.borrow_mut().remove(item)?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        crate::platform_impl::x11_hotkeys::item_removed(item, None);
+        Ok(())
     }
 
     /// Returns a list of menu items that has been added to this menu.
@@ -121,6 +150,96 @@ impl Menu {
         self.0.borrow().items()
     }
 
+    /// Returns the items of this menu and its submenus matching `query`, best matches first.
+    ///
+    /// This uses the same matching as [`CommandPalette::search`](crate::palette::CommandPalette::search),
+    /// so items are matched by their label and the labels of the submenus leading to them.
+    /// On gtk, use `Menu::reveal_item_for_gtk_window` to show a result in the menubar,
+    /// gtk4 can't reveal items as `gtk4::PopoverMenuBar` can't open its submenus programmatically.
+    pub fn search(&self, query: &str) -> Vec<PaletteEntry> {
+        CommandPalette::new(self)
+            .search(query)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the accelerators used by more than one item of this menu and its submenus,
+    /// including the default accelerators of [`PredefinedMenuItem`]s, with the items using them.
+    ///
+    /// Accelerators only differing by the sides of their modifiers are the same for native menus,
+    /// so they are reported as a conflict.
+    ///
+    /// [`PredefinedMenuItem`]: crate::PredefinedMenuItem
+    pub fn accelerator_conflicts(&self) -> Vec<(Accelerator, Vec<MenuItemKind>)> {
+        let mut accelerators = Vec::new();
+        collect_accelerators(self.items(), &mut accelerators);
+
+        let mut conflicts: Vec<(Accelerator, Vec<MenuItemKind>)> = Vec::new();
+        for (accelerator, item) in accelerators {
+            match conflicts
+                .iter_mut()
+                .find(|(a, _)| is_same_shortcut(a, &accelerator))
+            {
+                Some((_, items)) if items.iter().any(|i| i.id() == item.id()) => {}
+                Some((_, items)) => items.push(item),
+                None => conflicts.push((accelerator, vec![item])),
+            }
+        }
+        conflicts.retain(|(_, items)| items.len() > 1);
+        conflicts
+    }
+
+    /// Activates the enabled item of this menu or its submenus whose accelerator matches
+    /// `modifiers` and `key`, as if it was clicked, and returns the emitted [`MenuEvent`].
+    ///
+    /// This is useful to handle accelerators when the menu is drawn by the app itself
+    /// or hidden, for example with [`Menu::hide_for_gtk_window`].
+    ///
+    /// Items inside disabled submenus are skipped and [`CheckMenuItem`]s are toggled.
+    /// [`PredefinedMenuItem`]s are never activated as their actions are performed by the platform menus.
+    ///
+    /// [`CheckMenuItem`]: crate::CheckMenuItem
+    /// [`PredefinedMenuItem`]: crate::PredefinedMenuItem
+    pub fn handle_key(
+        &self,
+        modifiers: impl std::borrow::Borrow<Modifiers>,
+        key: impl std::borrow::Borrow<Code>,
+    ) -> Option<MenuEvent> {
+        let (modifiers, key) = (modifiers.borrow(), key.borrow());
+        let item = find_item_by_accelerator(self.items(), &|a| a.matches(modifiers, key))?;
+        Some(activate_item(item))
+    }
+
+    /// Same as [`Menu::handle_key`] but for the accelerators created with
+    /// [`Accelerator::new_logical`], matched against the character produced by a key press.
+    pub fn handle_logical_key(
+        &self,
+        modifiers: impl std::borrow::Borrow<Modifiers>,
+        key: &Key,
+    ) -> Option<MenuEvent> {
+        let modifiers = modifiers.borrow();
+        let item = find_item_by_accelerator(self.items(), &|a| a.matches_key(modifiers, key))?;
+        Some(activate_item(item))
+    }
+
+    /// Makes this menu reject accelerators that are already used by another item
+    /// of this menu or its submenus.
+    ///
+    /// While enabled, adding items to this menu or its submenus and setting the accelerator
+    /// of one of their items return [`Error::AcceleratorConflict`](crate::Error::AcceleratorConflict)
+    /// instead of introducing a duplicate. Existing duplicates are kept,
+    /// use [`Menu::accelerator_conflicts`] to find them.
+    pub fn set_strict_accelerators(&self, strict: bool) {
+        STRICT_MENUS.with(|menus| {
+            let mut menus = menus.borrow_mut();
+            menus.retain(|m| m.upgrade().is_some_and(|m| !Rc::ptr_eq(&m, &self.0)));
+            if strict {
+                menus.push(Rc::downgrade(&self.0));
+            }
+        })
+    }
+
     /// Adds this menu to a [`gtk::ApplicationWindow`]
     ///
     /// - `container`: this is an optional paramter to specify a container for the [`gtk::MenuBar`],
@@ -140,7 +259,7 @@ impl Menu {
     /// ## Panics:
     ///
     /// Panics if the gtk event loop hasn't been initialized on the thread.
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     pub fn init_for_gtk_window<W, C>(&self, window: &W, container: Option<&C>) -> crate::Result<()>
     where
         W: gtk::prelude::IsA<gtk::ApplicationWindow>,
@@ -151,6 +270,38 @@ impl Menu {
         self.0.borrow_mut().init_for_gtk_window(window, container)
     }
 
+    /// Adds this menu to a [`gtk4::ApplicationWindow`]
+    ///
+    /// - `container`: this is an optional paramter to specify a [`gtk4::Box`] to prepend the [`gtk4::PopoverMenuBar`] to,
+    /// otherwise the current child of the window is moved into a new vertical box below the menubar.
+    ///
+    /// The menu's items are exposed to the window as actions in the `muda` action group,
+    /// and their accelerators are handled by a [`gtk4::ShortcutController`] added to the window.
+    ///
+    /// ## Example:
+    /// ```no_run
+    /// # use gtk4::prelude::*;
+    /// let window = gtk4::ApplicationWindow::builder().build();
+    /// let vbox = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+    /// window.set_child(Some(&vbox));
+    /// let menu = muda::Menu::new();
+    /// // -- snip, add your menu items --
+    /// menu.init_for_gtk_window(&window, Some(&vbox));
+    /// // then proceed to add your widgets to the `vbox`
+    /// ```
+    ///
+    /// ## Panics:
+    ///
+    /// Panics if gtk hasn't been initialized on the thread.
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    pub fn init_for_gtk_window<W, C>(&self, window: &W, container: Option<&C>) -> crate::Result<()>
+    where
+        W: gtk4::prelude::IsA<gtk4::ApplicationWindow>,
+        C: gtk4::prelude::IsA<gtk4::Box>,
+    {
+        self.0.borrow_mut().init_for_gtk_window(window, container)
+    }
+
     /// Adds this menu to a win32 window.
     ///
     /// ##  Note about accelerators:
@@ -189,7 +340,7 @@ impl Menu {
     }
 
     /// Removes this menu from a [`gtk::ApplicationWindow`]
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     pub fn remove_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
     where
         W: gtk::prelude::IsA<gtk::ApplicationWindow>,
@@ -198,6 +349,15 @@ impl Menu {
         self.0.borrow_mut().remove_for_gtk_window(window)
     }
 
+    /// Removes this menu from a [`gtk4::ApplicationWindow`]
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    pub fn remove_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
+    where
+        W: gtk4::prelude::IsA<gtk4::ApplicationWindow>,
+    {
+        self.0.borrow_mut().remove_for_gtk_window(window)
+    }
+
     /// Removes this menu from a win32 window
     #[cfg(target_os = "windows")]
     pub fn remove_for_hwnd(&self, hwnd: isize) -> crate::Result<()> {
@@ -205,7 +365,7 @@ impl Menu {
     }
 
     /// Hides this menu from a [`gtk::ApplicationWindow`]
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     pub fn hide_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
     where
         W: gtk::prelude::IsA<gtk::ApplicationWindow>,
@@ -213,6 +373,15 @@ impl Menu {
         self.0.borrow_mut().hide_for_gtk_window(window)
     }
 
+    /// Hides this menu from a [`gtk4::ApplicationWindow`]
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    pub fn hide_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
+    where
+        W: gtk4::prelude::IsA<gtk4::ApplicationWindow>,
+    {
+        self.0.borrow_mut().hide_for_gtk_window(window)
+    }
+
     /// Hides this menu from a win32 window
     #[cfg(target_os = "windows")]
     pub fn hide_for_hwnd(&self, hwnd: isize) -> crate::Result<()> {
@@ -220,7 +389,7 @@ impl Menu {
     }
 
     /// Shows this menu on a [`gtk::ApplicationWindow`]
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     pub fn show_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
     where
         W: gtk::prelude::IsA<gtk::ApplicationWindow>,
@@ -228,6 +397,15 @@ impl Menu {
         self.0.borrow_mut().show_for_gtk_window(window)
     }
 
+    /// Shows this menu on a [`gtk4::ApplicationWindow`]
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    pub fn show_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
+    where
+        W: gtk4::prelude::IsA<gtk4::ApplicationWindow>,
+    {
+        self.0.borrow_mut().show_for_gtk_window(window)
+    }
+
     /// Shows this menu on a win32 window
     #[cfg(target_os = "windows")]
     pub fn show_for_hwnd(&self, hwnd: isize) -> crate::Result<()> {
@@ -235,7 +413,7 @@ impl Menu {
     }
 
     /// Returns whether this menu visible on a [`gtk::ApplicationWindow`]
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     pub fn is_visible_on_gtk_window<W>(&self, window: &W) -> bool
     where
         W: gtk::prelude::IsA<gtk::ApplicationWindow>,
@@ -243,7 +421,16 @@ impl Menu {
         self.0.borrow().is_visible_on_gtk_window(window)
     }
 
-    #[cfg(target_os = "linux")]
+    /// Returns whether this menu visible on a [`gtk4::ApplicationWindow`]
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    pub fn is_visible_on_gtk_window<W>(&self, window: &W) -> bool
+    where
+        W: gtk4::prelude::IsA<gtk4::ApplicationWindow>,
+    {
+        self.0.borrow().is_visible_on_gtk_window(window)
+    }
+
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     /// Returns the [`gtk::MenuBar`] that is associated with this window if it exists.
     /// This is useful to get information about the menubar for example its height.
     pub fn gtk_menubar_for_gtk_window<W>(self, window: &W) -> Option<gtk::MenuBar>
@@ -253,6 +440,96 @@ impl Menu {
         self.0.borrow().gtk_menubar_for_gtk_window(window)
     }
 
+    /// Opens the submenus of the menubar of this [`gtk::ApplicationWindow`] leading to `item`
+    /// and selects it, like the Help menu search of macOS does, so users can find where it is.
+    ///
+    /// Returns [`Error::NotAChildOfThisMenu`](crate::Error::NotAChildOfThisMenu) if `item` isn't
+    /// part of this menu or its submenus.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **gtk4**: Unsupported, `gtk4::PopoverMenuBar` can't open its submenus programmatically.
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
+    pub fn reveal_item_for_gtk_window<W>(
+        &self,
+        window: &W,
+        item: &dyn IsMenuItem,
+    ) -> crate::Result<()>
+    where
+        W: gtk::prelude::IsA<gtk::ApplicationWindow>,
+    {
+        self.0.borrow().reveal_item_for_gtk_window(window, item)
+    }
+
+    /// Searches this menu for `query`, see [`Menu::search`], and reveals the best match
+    /// in the menubar of this [`gtk::ApplicationWindow`], see [`Menu::reveal_item_for_gtk_window`].
+    ///
+    /// Returns the revealed entry, or `None` if nothing matched.
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
+    pub fn search_for_gtk_window<W>(
+        &self,
+        window: &W,
+        query: &str,
+    ) -> crate::Result<Option<PaletteEntry>>
+    where
+        W: gtk::prelude::IsA<gtk::ApplicationWindow>,
+    {
+        let Some(entry) = self.search(query).into_iter().next() else {
+            return Ok(None);
+        };
+        self.reveal_item_for_gtk_window(window, entry.item().as_ref())?;
+        Ok(Some(entry))
+    }
+
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    /// Returns the [`gtk4::PopoverMenuBar`] that is associated with this window if it exists.
+    /// This is useful to get information about the menubar for example its height.
+    pub fn gtk_menubar_for_gtk_window<W>(self, window: &W) -> Option<gtk4::PopoverMenuBar>
+    where
+        W: gtk4::prelude::IsA<gtk4::ApplicationWindow>,
+    {
+        self.0.borrow().gtk_menubar_for_gtk_window(window)
+    }
+
+    /// Returns a [`gio::MenuModel`](gtk::gio::MenuModel) mirroring this menu, whose items activate
+    /// [`gio::SimpleAction`](gtk::gio::SimpleAction)s registered on `app` under the `app.` prefix.
+    ///
+    /// This is useful for applications that use [`gtk::Application::set_menubar`] or a
+    /// [`gtk::MenuButton`] instead of a muda-managed [`gtk::MenuBar`]. Accelerators are registered
+    /// using [`gtk::prelude::GtkApplicationExt::set_accels_for_action`] and changes made to the
+    /// menu and its items afterwards are reflected in the returned model.
+    ///
+    /// Calling this method more than once with the same `app` returns the same model.
+    ///
+    /// #### Example:
+    /// ```no_run
+    /// # use muda::Menu;
+    /// # use gtk::prelude::*;
+    /// # let app = gtk::Application::new(None, Default::default());
+    /// let menu = Menu::new();
+    /// app.connect_startup(move |app| {
+    ///     let model = menu.gtk_menu_model_for_gtk_application(app).unwrap();
+    ///     app.set_menubar(Some(&model));
+    /// });
+    /// ```
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Linux:** Predefined menu items other than copy, cut, paste, select all and about are skipped.
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
+    pub fn gtk_menu_model_for_gtk_application<A>(
+        &self,
+        app: &A,
+    ) -> crate::Result<gtk::gio::MenuModel>
+    where
+        A: gtk::prelude::IsA<gtk::Application>,
+    {
+        use gtk::prelude::Cast;
+        self.0
+            .borrow_mut()
+            .gtk_menu_model_for_gtk_application(app.upcast_ref())
+    }
+
     /// Returns whether this menu visible on a on a win32 window
     #[cfg(target_os = "windows")]
     pub fn is_visible_on_hwnd(&self, hwnd: isize) -> bool {
@@ -293,7 +570,7 @@ impl ContextMenu for Menu {
         self.0.borrow().detach_menu_subclass_from_hwnd(hwnd)
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     fn show_context_menu_for_gtk_window(
         &self,
         window: &gtk::ApplicationWindow,
@@ -304,11 +581,27 @@ impl ContextMenu for Menu {
             .show_context_menu_for_gtk_window(window, position)
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "gtk4")))]
     fn gtk_context_menu(&self) -> gtk::Menu {
         self.0.borrow_mut().gtk_context_menu()
     }
 
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    fn show_context_menu_for_gtk_window(
+        &self,
+        window: &gtk4::ApplicationWindow,
+        position: Option<Position>,
+    ) {
+        self.0
+            .borrow_mut()
+            .show_context_menu_for_gtk_window(window, position)
+    }
+
+    #[cfg(all(target_os = "linux", feature = "gtk4"))]
+    fn gtk_context_menu(&self) -> gtk4::PopoverMenu {
+        self.0.borrow_mut().gtk_context_menu()
+    }
+
     #[cfg(target_os = "macos")]
     fn show_context_menu_for_nsview(&self, view: cocoa::base::id, position: Option<Position>) {
         self.0
@@ -321,3 +614,329 @@ impl ContextMenu for Menu {
         self.0.borrow().ns_menu()
     }
 }
+
+/// Appends the accelerators of `items` and all their descendants to `accelerators`.
+fn collect_accelerators(
+    items: Vec<MenuItemKind>,
+    accelerators: &mut Vec<(Accelerator, MenuItemKind)>,
+) {
+    for item in items {
+        for accelerator in item.child().accelerators() {
+            accelerators.push((accelerator, item.clone()));
+        }
+        if let MenuItemKind::Submenu(submenu) = &item {
+            collect_accelerators(submenu.items(), accelerators);
+        }
+    }
+}
+
+/// Returns whether the native menus register `a` and `b` as the same shortcut,
+/// they can't tell the sides of the modifiers apart.
+fn is_same_shortcut(a: &Accelerator, b: &Accelerator) -> bool {
+    a.mods == b.mods
+        && match (a.logical, b.logical) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => a.key == b.key,
+            _ => false,
+        }
+}
+
+/// Returns the first enabled item, outside of disabled submenus,
+/// with an accelerator matching `modifiers` and `key`.
+fn find_item_by_accelerator(
+    items: Vec<MenuItemKind>,
+    matches: &dyn Fn(&Accelerator) -> bool,
+) -> Option<MenuItemKind> {
+    items.into_iter().find_map(|item| {
+        if !item.child().is_enabled() {
+            return None;
+        }
+
+        match &item {
+            MenuItemKind::Submenu(submenu) => find_item_by_accelerator(submenu.items(), matches),
+            MenuItemKind::Predefined(_) => None,
+            _ => {
+                let accelerators = item.child().accelerators();
+                accelerators.iter().any(matches).then_some(item)
+            }
+        }
+    })
+}
+
+pub(crate) fn activate_item(item: MenuItemKind) -> MenuEvent {
+    if let MenuItemKind::Check(check) = &item {
+        check.set_checked(!check.is_checked());
+    }
+
+    let event = MenuEvent { id: item.id() };
+    MenuEvent::send(event);
+    event
+}
+
+/// Returns the submenus leading to the item `id`, followed by the item itself.
+#[cfg(all(target_os = "linux", not(feature = "gtk4")))]
+pub(crate) fn find_item_path(items: Vec<MenuItemKind>, id: u32) -> Option<Vec<MenuItemKind>> {
+    items.into_iter().find_map(|item| {
+        if item.id() == id {
+            return Some(vec![item]);
+        }
+
+        let mut path = find_item_path(item.as_submenu()?.items(), id)?;
+        path.insert(0, item);
+        Some(path)
+    })
+}
+
+fn contains_item(items: Vec<MenuItemKind>, id: u32) -> bool {
+    items.into_iter().any(|item| {
+        item.id() == id
+            || item
+                .as_submenu()
+                .is_some_and(|submenu| contains_item(submenu.items(), id))
+    })
+}
+
+/// Returns an error if giving the items with the ids in `new` their accelerators would
+/// duplicate an accelerator of a strict menu that is or contains the item with `container_id`.
+fn check_strict_accelerators(container_id: u32, new: &[(Accelerator, u32)]) -> crate::Result<()> {
+    let menus = STRICT_MENUS.with(|menus| {
+        let mut menus = menus.borrow_mut();
+        menus.retain(|m| m.strong_count() > 0);
+        menus.iter().filter_map(Weak::upgrade).collect::<Vec<_>>()
+    });
+
+    for menu in menus.into_iter().map(Menu) {
+        if menu.id() != container_id && !contains_item(menu.items(), container_id) {
+            continue;
+        }
+
+        let mut accelerators = Vec::new();
+        collect_accelerators(menu.items(), &mut accelerators);
+        // the new accelerators replace those of the same items
+        let mut taken = accelerators
+            .into_iter()
+            .filter(|(_, item)| !new.iter().any(|(_, id)| *id == item.id()))
+            .map(|(accelerator, item)| (accelerator, item.id()))
+            .collect::<Vec<_>>();
+
+        for (accelerator, id) in new {
+            if taken
+                .iter()
+                .any(|(a, i)| i != id && is_same_shortcut(a, accelerator))
+            {
+                return Err(crate::Error::AcceleratorConflict(*accelerator));
+            }
+            taken.push((*accelerator, *id));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the accelerators of `item` and its descendants before adding it to the menu
+/// or submenu with `container_id`, see [`Menu::set_strict_accelerators`].
+pub(crate) fn check_strict_accelerators_for_item(
+    container_id: u32,
+    item: &dyn IsMenuItem,
+) -> crate::Result<()> {
+    if STRICT_MENUS.with(|menus| menus.borrow().is_empty()) {
+        return Ok(());
+    }
+
+    let mut accelerators = Vec::new();
+    collect_accelerators(vec![item.kind()], &mut accelerators);
+    let new = accelerators
+        .into_iter()
+        .map(|(accelerator, item)| (accelerator, item.id()))
+        .collect::<Vec<_>>();
+    check_strict_accelerators(container_id, &new)
+}
+
+/// Checks `accelerators` before setting them on the item with `id`, see [`Menu::set_strict_accelerators`].
+pub(crate) fn check_strict_accelerator<'a>(
+    id: u32,
+    accelerators: impl IntoIterator<Item = &'a Accelerator>,
+) -> crate::Result<()> {
+    if STRICT_MENUS.with(|menus| menus.borrow().is_empty()) {
+        return Ok(());
+    }
+
+    let new = accelerators
+        .into_iter()
+        .map(|accelerator| (*accelerator, id))
+        .collect::<Vec<_>>();
+    check_strict_accelerators(id, &new)
+}
+
+#[test]
+fn test_accelerator_conflicts() {
+    use crate::{accelerator::ModifierSides, MenuItem, PredefinedMenuItem, Submenu};
+
+    let accelerator = |s: &str| s.parse::<Accelerator>().ok();
+
+    let copy = MenuItem::new("Copy", true, accelerator("Ctrl+KeyC"));
+    let open = MenuItem::new("Open", true, accelerator("Ctrl+KeyO"));
+    let open_recent = MenuItem::new(
+        "Open Recent",
+        true,
+        accelerator("Ctrl+KeyO").map(|a| {
+            a.with_sides(ModifierSides {
+                left: Modifiers::CONTROL,
+                right: Modifiers::empty(),
+            })
+        }),
+    );
+    let save = MenuItem::new("Save", true, None);
+    save.set_accelerators(accelerator("Ctrl+KeyS").into_iter().cycle().take(2))
+        .unwrap();
+
+    let recent = Submenu::with_items("Recent", true, &[&open_recent]).unwrap();
+    let file = Submenu::with_items("File", true, &[&open, &recent, &save]).unwrap();
+    let edit =
+        Submenu::with_items("Edit", true, &[&copy, &PredefinedMenuItem::copy(None)]).unwrap();
+    let menu = Menu::with_items(&[&file, &edit]).unwrap();
+
+    let conflicts = menu
+        .accelerator_conflicts()
+        .into_iter()
+        .map(|(a, items)| (a, items.iter().map(|i| i.id()).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0].0, accelerator("Ctrl+KeyO").unwrap());
+    assert_eq!(conflicts[0].1, [open.id(), open_recent.id()]);
+    assert_eq!(conflicts[1].0, accelerator("Ctrl+KeyC").unwrap());
+    assert_eq!(conflicts[1].1[0], copy.id());
+    assert_eq!(conflicts[1].1.len(), 2);
+}
+
+#[test]
+fn test_strict_accelerators() {
+    use crate::{accelerator::ModifierSides, MenuItem, Submenu};
+
+    let accelerator = |s: &str| s.parse::<Accelerator>().ok();
+
+    let save = MenuItem::new("Save", true, accelerator("Ctrl+KeyS"));
+    let file = Submenu::with_items("File", true, &[&save]).unwrap();
+    let menu = Menu::with_items(&[&file]).unwrap();
+    menu.set_strict_accelerators(true);
+
+    let save_as = MenuItem::new("Save As", true, accelerator("Ctrl+KeyS"));
+    assert!(matches!(
+        menu.append(&save_as),
+        Err(crate::Error::AcceleratorConflict(_))
+    ));
+    assert!(matches!(
+        menu.insert(&save_as, 0),
+        Err(crate::Error::AcceleratorConflict(_))
+    ));
+    assert!(matches!(
+        file.append(&save_as),
+        Err(crate::Error::AcceleratorConflict(_))
+    ));
+    assert_eq!(file.items().len(), 1);
+
+    let open = MenuItem::new("Open", true, accelerator("Ctrl+KeyO"));
+    file.append(&open).unwrap();
+    let left_save = accelerator("Ctrl+KeyS").map(|a| {
+        a.with_sides(ModifierSides {
+            left: Modifiers::CONTROL,
+            right: Modifiers::empty(),
+        })
+    });
+    for new in [accelerator("Ctrl+KeyS"), left_save] {
+        assert!(matches!(
+            open.set_accelerator(new),
+            Err(crate::Error::AcceleratorConflict(_))
+        ));
+    }
+    assert_eq!(open.accelerator(), accelerator("Ctrl+KeyO"));
+
+    // an item can take its own accelerators again
+    save.set_accelerator(accelerator("Ctrl+KeyS")).unwrap();
+    save.set_accelerators(accelerator("Ctrl+KeyS").into_iter().cycle().take(2))
+        .unwrap();
+
+    // dropped menus are pruned from the strict menus
+    let other = Menu::new();
+    other.set_strict_accelerators(true);
+    drop(other);
+    menu.append(&MenuItem::new("Quit", true, accelerator("Ctrl+KeyQ")))
+        .unwrap();
+    assert_eq!(STRICT_MENUS.with(|menus| menus.borrow().len()), 1);
+
+    menu.set_strict_accelerators(false);
+    menu.append(&save_as).unwrap();
+    assert!(STRICT_MENUS.with(|menus| menus.borrow().is_empty()));
+}
+
+#[test]
+fn test_handle_key() {
+    use crate::{CheckMenuItem, MenuItem, PredefinedMenuItem, Submenu};
+
+    let accelerator = |s: &str| s.parse::<Accelerator>().ok();
+
+    let disabled_save = MenuItem::new("Save", false, accelerator("Ctrl+KeyS"));
+    let save = MenuItem::new("Save", true, accelerator("Ctrl+KeyS"));
+    let hidden = MenuItem::new("Open", true, accelerator("Ctrl+KeyO"));
+    let wrap = CheckMenuItem::new("Wrap", true, false, accelerator("Alt+KeyZ"));
+    let zoom = MenuItem::new(
+        "Zoom In",
+        true,
+        Some(Accelerator::new_logical(Some(Modifiers::CONTROL), '+')),
+    );
+
+    let recent = Submenu::with_items("Recent", false, &[&hidden]).unwrap();
+    let file = Submenu::with_items("File", true, &[&disabled_save, &recent, &save]).unwrap();
+    let edit = Submenu::with_items("Edit", true, &[&PredefinedMenuItem::copy(None)]).unwrap();
+    let view = Submenu::with_items("View", true, &[&wrap, &zoom]).unwrap();
+    let menu = Menu::with_items(&[&file, &edit, &view]).unwrap();
+
+    let event = menu.handle_key(Modifiers::CONTROL, Code::KeyS).unwrap();
+    assert_eq!(event.id, save.id());
+    assert!(menu.handle_key(Modifiers::CONTROL, Code::KeyO).is_none());
+    assert!(menu.handle_key(Modifiers::CONTROL, Code::KeyC).is_none());
+
+    let event = menu.handle_key(Modifiers::ALT, Code::KeyZ).unwrap();
+    assert_eq!(event.id, wrap.id());
+    assert!(wrap.is_checked());
+    menu.handle_key(Modifiers::ALT, Code::KeyZ).unwrap();
+    assert!(!wrap.is_checked());
+
+    let event = menu
+        .handle_logical_key(Modifiers::CONTROL, &Key::Character("+".into()))
+        .unwrap();
+    assert_eq!(event.id, zoom.id());
+    assert!(menu.handle_key(Modifiers::CONTROL, Code::Equal).is_none());
+    assert!(menu
+        .handle_logical_key(Modifiers::CONTROL, &Key::Character("s".into()))
+        .is_none());
+}
+
+#[cfg(all(target_os = "linux", not(feature = "gtk4")))]
+#[test]
+fn test_find_item_path() {
+    use crate::{MenuItem, Submenu};
+
+    let top = MenuItem::new("Top", true, None);
+    let nested = MenuItem::new("Nested", true, None);
+    let inner = Submenu::with_items("Inner", true, &[&nested]).unwrap();
+    let outer = Submenu::with_items("Outer", true, &[&inner]).unwrap();
+    let menu = Menu::with_items(&[&top, &outer]).unwrap();
+
+    let ids = |path: Vec<MenuItemKind>| path.iter().map(|i| i.id()).collect::<Vec<_>>();
+    assert_eq!(
+        ids(find_item_path(menu.items(), top.id()).unwrap()),
+        [top.id()]
+    );
+    assert_eq!(
+        ids(find_item_path(menu.items(), nested.id()).unwrap()),
+        [outer.id(), inner.id(), nested.id()]
+    );
+    assert_eq!(
+        ids(find_item_path(menu.items(), inner.id()).unwrap()),
+        [outer.id(), inner.id()]
+    );
+
+    let other = MenuItem::new("Other", true, None);
+    assert!(find_item_path(menu.items(), other.id()).is_none());
+}
@@ -0,0 +1,278 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Command palettes list the items of a menu as a flat, searchable list of commands.
+//!
+//! A [`CommandPalette`] is a snapshot of the items of a [`Menu`] and its submenus,
+//! so an application can offer a palette, usually bound to `CmdOrCtrl+Shift+P`,
+//! without defining its commands twice:
+//!
+//! ```no_run
+//! # use muda::{Menu, palette::CommandPalette};
+//! # let menu = Menu::new();
+//! let palette = CommandPalette::new(&menu);
+//! for entry in palette.search("fnd rep") {
+//!     println!("{} {:?}", entry.breadcrumb(), entry.accelerator());
+//! }
+//! // when the user picks the first entry
+//! if let Some(entry) = palette.search("fnd rep").first() {
+//!     entry.invoke();
+//! }
+//! ```
+//!
+//! Predefined menu items are left out as they perform native actions instead of
+//! emitting [`MenuEvent`]s. Create a new palette whenever the menu changes.
+
+use crate::{
+    accelerator::DisplayStyle, menu::activate_item, util::strip_mnemonic, Menu, MenuEvent,
+    MenuItemKind,
+};
+
+/// The separator used by [`PaletteEntry::breadcrumb`].
+pub const BREADCRUMB_SEPARATOR: &str = " › ";
+
+/// A flat list of the commands of a menu, see the [module docs](self).
+#[derive(Clone)]
+pub struct CommandPalette {
+    entries: Vec<PaletteEntry>,
+}
+
+impl CommandPalette {
+    /// Creates a palette with the items of `menu` and its submenus, in menu order.
+    pub fn new(menu: &Menu) -> Self {
+        Self::from_items(&menu.items())
+    }
+
+    /// Creates a palette with `items` and, for submenus, their descendants, in order.
+    pub fn from_items(items: &[MenuItemKind]) -> Self {
+        let mut entries = Vec::new();
+        collect_entries(items, &[], true, &mut entries);
+        Self { entries }
+    }
+
+    /// Returns all the entries of this palette, in menu order.
+    pub fn entries(&self) -> &[PaletteEntry] {
+        &self.entries
+    }
+
+    /// Returns the entries matching `query`, best matches first.
+    ///
+    /// Entries are matched by their label and, with a lower rank, by their breadcrumb,
+    /// see [`fuzzy_score`]. Equally ranked entries keep the menu order, and an empty
+    /// query returns all the entries.
+    pub fn search(&self, query: &str) -> Vec<&PaletteEntry> {
+        let mut matches = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.score(query).map(|score| (score, entry)))
+            .collect::<Vec<_>>();
+        // `sort_by` is stable, so ties keep the menu order
+        matches.sort_by(|(a, a_entry), (b, b_entry)| {
+            b.cmp(a)
+                .then_with(|| a_entry.label.len().cmp(&b_entry.label.len()))
+        });
+        matches.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+/// A command of a [`CommandPalette`].
+#[derive(Clone)]
+pub struct PaletteEntry {
+    item: MenuItemKind,
+    path: Vec<String>,
+    label: String,
+    accelerator: Option<String>,
+    enabled: bool,
+    checked: Option<bool>,
+}
+
+impl PaletteEntry {
+    /// Returns the id of the menu item of this entry.
+    pub fn id(&self) -> u32 {
+        self.item.id()
+    }
+
+    /// Returns the menu item of this entry.
+    pub fn item(&self) -> &MenuItemKind {
+        &self.item
+    }
+
+    /// Returns the labels of the submenus leading to this entry, without mnemonics.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Returns the label of this entry, without mnemonics.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the path and label of this entry joined with [`BREADCRUMB_SEPARATOR`],
+    /// for example `Edit › Find › Replace`.
+    pub fn breadcrumb(&self) -> String {
+        self.path
+            .iter()
+            .map(String::as_str)
+            .chain([self.label.as_str()])
+            .collect::<Vec<_>>()
+            .join(BREADCRUMB_SEPARATOR)
+    }
+
+    /// Returns the accelerator of this entry formatted with the [`DisplayStyle`] of the current platform.
+    pub fn accelerator(&self) -> Option<&str> {
+        self.accelerator.as_deref()
+    }
+
+    /// Returns whether this entry can be invoked, which requires its item and all the submenus leading to it to be enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the checked state of this entry if its item is a [`CheckMenuItem`](crate::CheckMenuItem).
+    pub fn checked(&self) -> Option<bool> {
+        self.checked
+    }
+
+    /// Activates the item of this entry like a click would, toggling check menu items
+    /// and emitting a [`MenuEvent`], which is returned.
+    ///
+    /// Returns `None` without doing anything if this entry is disabled.
+    pub fn invoke(&self) -> Option<MenuEvent> {
+        self.enabled.then(|| activate_item(self.item.clone()))
+    }
+
+    fn score(&self, query: &str) -> Option<u32> {
+        fuzzy_score(query, &self.label).or_else(|| {
+            let text = self.path.iter().chain([&self.label]).cloned();
+            fuzzy_score(query, &text.collect::<Vec<_>>().join(" ")).map(|score| score / 2)
+        })
+    }
+}
+
+/// Scores how well `query` matches `text`, higher is better, or returns `None` if it doesn't.
+///
+/// The characters of `query`, ignoring whitespace and case, have to appear in `text` in order.
+/// Matches at the start of words and consecutive matches score higher, so `fnd rep`
+/// matches `Find and Replace` better than `Find Duplicates`. An empty query matches everything with a score of `0`.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let mut query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut consecutive = false;
+    for c in text.chars() {
+        let Some(next) = query.peek() else {
+            break;
+        };
+
+        if c.to_lowercase().eq([*next]) {
+            score += 1;
+            let word_start = match previous {
+                None => true,
+                Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && c.is_uppercase()),
+            };
+            if word_start {
+                score += 8;
+            }
+            if consecutive {
+                score += 4;
+            }
+            consecutive = true;
+            query.next();
+        } else {
+            consecutive = false;
+        }
+        previous = Some(c);
+    }
+
+    query.peek().is_none().then_some(score)
+}
+
+fn collect_entries(
+    items: &[MenuItemKind],
+    path: &[String],
+    enabled: bool,
+    entries: &mut Vec<PaletteEntry>,
+) {
+    for item in items {
+        let child = item.child();
+        let enabled = enabled && child.is_enabled();
+        let label = strip_mnemonic(child.text());
+        let accelerator = child.accelerator();
+        drop(child);
+
+        match item {
+            MenuItemKind::Submenu(submenu) => {
+                let mut path = path.to_vec();
+                path.push(label);
+                collect_entries(&submenu.items(), &path, enabled, entries);
+            }
+            MenuItemKind::Predefined(_) => {}
+            _ => entries.push(PaletteEntry {
+                item: item.clone(),
+                path: path.to_vec(),
+                label,
+                accelerator: accelerator.map(|a| a.to_display_string(DisplayStyle::default())),
+                enabled,
+                checked: item.as_check_menuitem().map(|i| i.is_checked()),
+            }),
+        }
+    }
+}
+
+#[test]
+fn test_fuzzy_score() {
+    assert_eq!(fuzzy_score("", "Anything"), Some(0));
+    assert_eq!(fuzzy_score("xyz", "Find"), None);
+    assert_eq!(fuzzy_score("dnif", "Find"), None);
+    assert!(fuzzy_score("FIND", "find").is_some());
+
+    let replace = fuzzy_score("fnd rep", "Find and Replace").unwrap();
+    let duplicates = fuzzy_score("fnd rep", "Find Duplicates and Repair");
+    assert!(duplicates.is_none() || duplicates.unwrap() <= replace);
+    assert!(fuzzy_score("fr", "Find Replace") > fuzzy_score("fr", "Free"));
+    assert!(fuzzy_score("sa", "SaveAs") > fuzzy_score("sa", "Rosa"));
+    assert!(fuzzy_score("sA", "saveAll") > fuzzy_score("sa", "Visa"));
+}
+
+#[test]
+fn test_palette_entries() {
+    use crate::{accelerator::Accelerator, CheckMenuItem, IsMenuItem, MenuItem};
+
+    let save = MenuItem::new(
+        "&Save",
+        true,
+        Some("CmdOrCtrl+KeyS".parse::<Accelerator>().unwrap()),
+    );
+    let save_all = MenuItem::new("Save &All", false, None);
+    let wrap = CheckMenuItem::new("&Word && Line Wrap", true, true, None);
+    let palette = CommandPalette::from_items(&[save.kind(), save_all.kind(), wrap.kind()]);
+
+    let entries = palette.entries();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].label(), "Save");
+    assert_eq!(entries[0].breadcrumb(), "Save");
+    assert_eq!(entries[0].id(), save.id());
+    assert!(entries[0].accelerator().is_some());
+    assert_eq!(entries[0].checked(), None);
+    assert_eq!(entries[1].label(), "Save All");
+    assert!(!entries[1].is_enabled());
+    assert!(entries[1].invoke().is_none());
+    assert_eq!(entries[2].label(), "Word & Line Wrap");
+    assert_eq!(entries[2].checked(), Some(true));
+
+    let results = palette.search("sav");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].id(), save.id());
+    assert_eq!(palette.search("wrap")[0].id(), wrap.id());
+    assert!(palette.search("quit").is_empty());
+
+    let event = entries[2].invoke().unwrap();
+    assert_eq!(event.id(), wrap.id());
+    assert!(!wrap.is_checked());
+}
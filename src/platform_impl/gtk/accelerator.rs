@@ -24,6 +24,13 @@ pub fn from_gtk_mnemonic<S: AsRef<str>>(string: S) -> String {
 }
 
 pub fn parse_accelerator(accelerator: &Accelerator) -> crate::Result<(gdk::ModifierType, u32)> {
+    // gtk matches keyvals through the current keymap, so a logical key
+    // only needs the keyval of its character.
+    if let Some(c) = accelerator.logical {
+        let key = gdk::keys::Key::from_unicode(c).to_lower();
+        return Ok((modifiers_to_gdk_modifier_type(accelerator.mods), *key));
+    }
+
     let key = match &accelerator.key {
         Code::KeyA => 'A' as u32,
         Code::KeyB => 'B' as u32,
@@ -85,6 +92,12 @@ pub fn parse_accelerator(accelerator: &Accelerator) -> crate::Result<(gdk::Modif
     Ok((modifiers_to_gdk_modifier_type(accelerator.mods), key))
 }
 
+/// Returns the keyval of `accelerator`, which is also its X11 keysym.
+#[cfg(feature = "x11-global-hotkeys")]
+pub fn accelerator_keysym(accelerator: &Accelerator) -> crate::Result<u32> {
+    parse_accelerator(accelerator).map(|(_, key)| key)
+}
+
 fn modifiers_to_gdk_modifier_type(modifiers: Modifiers) -> gdk::ModifierType {
     let mut result = gdk::ModifierType::empty();
 
@@ -101,9 +114,22 @@ fn modifiers_to_gdk_modifier_type(modifiers: Modifiers) -> gdk::ModifierType {
         modifiers.contains(Modifiers::SHIFT),
     );
     result.set(
-        gdk::ModifierType::META_MASK,
+        gdk::ModifierType::SUPER_MASK,
         modifiers.contains(Modifiers::SUPER),
     );
+    result.set(
+        gdk::ModifierType::HYPER_MASK,
+        modifiers.contains(Modifiers::HYPER),
+    );
+    result.set(
+        gdk::ModifierType::META_MASK,
+        modifiers.contains(Modifiers::META),
+    );
+    // AltGr is ISO_Level3_Shift, which X11 puts on Mod5
+    result.set(
+        gdk::ModifierType::MOD5_MASK,
+        modifiers.contains(Modifiers::ALT_GRAPH),
+    );
 
     result
 }
@@ -172,8 +198,72 @@ fn key_to_raw_key(src: &Code) -> Option<gdk::keys::Key> {
         Code::ArrowLeft => Left,
         Code::ArrowRight => Right,
 
+        Code::Numpad0 => KP_0,
+        Code::Numpad1 => KP_1,
+        Code::Numpad2 => KP_2,
+        Code::Numpad3 => KP_3,
+        Code::Numpad4 => KP_4,
+        Code::Numpad5 => KP_5,
+        Code::Numpad6 => KP_6,
+        Code::Numpad7 => KP_7,
+        Code::Numpad8 => KP_8,
+        Code::Numpad9 => KP_9,
+        Code::NumpadAdd => KP_Add,
+        Code::NumpadComma => KP_Separator,
+        Code::NumpadDecimal => KP_Decimal,
+        Code::NumpadDivide => KP_Divide,
+        Code::NumpadEnter => KP_Enter,
+        Code::NumpadEqual => KP_Equal,
+        Code::NumpadMultiply => KP_Multiply,
+        Code::NumpadSubtract => KP_Subtract,
+
         Code::ContextMenu => Menu,
+        Code::Help => Help,
         Code::WakeUp => WakeUp,
+
+        Code::AudioVolumeDown => AudioLowerVolume,
+        Code::AudioVolumeUp => AudioRaiseVolume,
+        Code::AudioVolumeMute => AudioMute,
+        Code::MediaPlayPause => AudioPlay,
+        Code::MediaStop => AudioStop,
+        Code::MediaTrackNext => AudioNext,
+        Code::MediaTrackPrevious => AudioPrev,
+        Code::MediaSelect => AudioMedia,
+
+        Code::BrowserBack => Back,
+        Code::BrowserForward => Forward,
+        Code::BrowserRefresh => Refresh,
+        Code::BrowserStop => Stop,
+        Code::BrowserSearch => Search,
+        Code::BrowserFavorites => Favorites,
+        Code::BrowserHome => HomePage,
+        Code::LaunchMail => Mail,
+        Code::LaunchApp1 => MyComputer,
+        Code::LaunchApp2 => Calculator,
+
+        // The keysyms of these keys on the layouts they are found on.
+        Code::IntlBackslash => less,
+        Code::IntlRo => backslash,
+        Code::IntlYen => yen,
+        Code::Convert => Henkan,
+        Code::NonConvert => Muhenkan,
+        Code::KanaMode => Hiragana_Katakana,
+        Code::Lang1 => Hangul,
+        Code::Lang2 => Hangul_Hanja,
+        Code::Lang3 => Katakana,
+        Code::Lang4 => Hiragana,
+        Code::Lang5 => Zenkaku_Hankaku,
         _ => return None,
     })
 }
+
+#[test]
+fn test_parse_accelerator_codes() {
+    for code in crate::accelerator::PARSEABLE_CODES {
+        let accelerator = Accelerator::new(None, *code);
+        assert!(
+            parse_accelerator(&accelerator).is_ok(),
+            "{code} isn't mapped"
+        );
+    }
+}
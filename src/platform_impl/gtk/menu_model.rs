@@ -0,0 +1,508 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Export of menus as [`gio::MenuModel`]s backed by [`gio::SimpleAction`]s
+//! registered on a [`gtk::Application`].
+
+use std::sync::atomic::Ordering;
+
+use gtk::{gio, prelude::*};
+
+use super::{
+    accelerator::{parse_accelerator, to_gtk_mnemonic},
+    show_about_dialog, Menu, MenuChild,
+};
+use crate::{items::PredefinedMenuItemType, MenuEvent, MenuItemKind, MenuItemType};
+
+/// Custom attribute storing the id of the [`MenuChild`] an item was created for,
+/// used to find the item again inside its section.
+const MUDA_ID_ATTRIBUTE: &str = "muda-id";
+
+impl Menu {
+    pub fn gtk_menu_model_for_gtk_application(
+        &mut self,
+        app: &gtk::Application,
+    ) -> crate::Result<gio::MenuModel> {
+        if let Some((_, gio_menu)) = self.gio_menus.iter().find(|(a, _)| a == app) {
+            return Ok(gio_menu.clone().upcast());
+        }
+
+        let gio_menu = gio::Menu::new();
+        sync_gio_menu(&gio_menu, self.items(), app)?;
+        self.gio_menus.push((app.clone(), gio_menu.clone()));
+
+        Ok(gio_menu.upcast())
+    }
+
+    pub(super) fn sync_gio_menus(&self) -> crate::Result<()> {
+        for (app, gio_menu) in &self.gio_menus {
+            sync_gio_menu(gio_menu, self.items(), app)?;
+        }
+        Ok(())
+    }
+}
+
+/// Submenu methods
+impl MenuChild {
+    pub fn gtk_menu_model_for_gtk_application(
+        &mut self,
+        app: &gtk::Application,
+    ) -> crate::Result<gio::MenuModel> {
+        self.gio_menu_for_gtk_application(app).map(|m| m.upcast())
+    }
+
+    fn gio_menu_for_gtk_application(&mut self, app: &gtk::Application) -> crate::Result<gio::Menu> {
+        if let Some((_, gio_menu)) = self.gio_menus.iter().find(|(a, _)| a == app) {
+            return Ok(gio_menu.clone());
+        }
+
+        let gio_menu = gio::Menu::new();
+        sync_gio_menu(&gio_menu, self.items(), app)?;
+        self.gio_menus.push((app.clone(), gio_menu.clone()));
+
+        Ok(gio_menu)
+    }
+
+    pub(super) fn sync_gio_menus(&self) -> crate::Result<()> {
+        for (app, gio_menu) in &self.gio_menus {
+            sync_gio_menu(gio_menu, self.items(), app)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared methods
+impl MenuChild {
+    /// Applies `f` to this item in every exported section it is still part of,
+    /// and forgets about the sections it has been removed from.
+    pub(super) fn update_gio_items<F: Fn(&gio::MenuItem)>(&mut self, f: F) {
+        let id = self.id;
+        self.gio_sections
+            .retain(|section| match gio_item_position(section, id) {
+                Some(position) => {
+                    let item = gio::MenuItem::from_model(section, position);
+                    f(&item);
+                    section.remove(position);
+                    section.insert_item(position, &item);
+                    true
+                }
+                None => false,
+            });
+    }
+
+    pub(super) fn set_gio_accelerator(&mut self) -> crate::Result<()> {
+        if self.has_display_only_accelerator() {
+            let accel = self.gio_accel_name()?;
+            self.update_gio_items(|item| {
+                item.set_attribute_value("accel", accel.as_ref().map(|a| a.to_variant()).as_ref())
+            });
+        } else {
+            for (app, action) in self.gio_actions.borrow().iter() {
+                set_accels_for_action(app, action, self.gio_accel_name()?);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_gio_icon(&mut self) {
+        let icon = self.icon_name.as_deref().map(gio::ThemedIcon::new);
+        self.update_gio_items(|item| match &icon {
+            Some(icon) => item.set_icon(icon),
+            None => item.set_attribute_value(&gio::MENU_ATTRIBUTE_ICON, None),
+        });
+    }
+
+    /// Unregisters the actions of this item, and the items of its submenu, from all applications
+    /// once it is no longer part of any exported menu.
+    pub(super) fn remove_unused_gio_items(&mut self) {
+        let id = self.id;
+        self.gio_sections
+            .retain(|section| gio_item_position(section, id).is_some());
+        if !self.gio_sections.is_empty() {
+            return;
+        }
+
+        for (app, action) in self.gio_actions.borrow_mut().drain(..) {
+            set_accels_for_action(&app, &action, None);
+            app.remove_action(&action.name());
+        }
+
+        if self.item_type == MenuItemType::Submenu {
+            for (_, gio_menu) in self.gio_menus.drain(..) {
+                clear_gio_menu(&gio_menu);
+            }
+            for item in self.items() {
+                item.child_mut().remove_unused_gio_items();
+            }
+        }
+    }
+
+    /// Returns whether this item is shown in exported menus, which only support
+    /// the predefined items whose actions can be performed from an action.
+    fn has_gio_item(&self) -> bool {
+        self.item_type != MenuItemType::Predefined
+            || matches!(
+                self.predefined_item_type,
+                PredefinedMenuItemType::Copy
+                    | PredefinedMenuItemType::Cut
+                    | PredefinedMenuItemType::Paste
+                    | PredefinedMenuItemType::SelectAll
+                    | PredefinedMenuItemType::About(_)
+            )
+    }
+
+    fn create_gio_item(&mut self, app: &gtk::Application) -> crate::Result<Option<gio::MenuItem>> {
+        if !self.has_gio_item() {
+            return Ok(None);
+        }
+
+        let label = to_gtk_mnemonic(&self.text);
+        let item = match self.item_type {
+            MenuItemType::Submenu => {
+                let submenu = self.gio_menu_for_gtk_application(app)?;
+                gio::MenuItem::new_submenu(Some(&label), &submenu)
+            }
+            _ => gio::MenuItem::new(Some(&label), None),
+        };
+
+        let action = self.gio_action_for_gtk_application(app)?;
+        let detailed_action = format!("app.{}", action.name());
+        if self.item_type == MenuItemType::Submenu {
+            // the enabled state of a submenu is controlled by its `submenu-action`
+            item.set_attribute_value("submenu-action", Some(&detailed_action.to_variant()));
+        } else {
+            item.set_detailed_action(&detailed_action);
+        }
+
+        if self.has_display_only_accelerator() {
+            if let Some(accel) = self.gio_accel_name()? {
+                item.set_attribute_value("accel", Some(&accel.to_variant()));
+            }
+        }
+
+        if let Some(icon_name) = &self.icon_name {
+            item.set_icon(&gio::ThemedIcon::new(icon_name));
+        }
+
+        item.set_attribute_value(MUDA_ID_ATTRIBUTE, Some(&self.id.to_variant()));
+
+        Ok(Some(item))
+    }
+
+    fn gio_action_for_gtk_application(
+        &mut self,
+        app: &gtk::Application,
+    ) -> crate::Result<gio::SimpleAction> {
+        if let Some((_, action)) = self.gio_actions.borrow().iter().find(|(a, _)| a == app) {
+            return Ok(action.clone());
+        }
+
+        let name = format!("muda-{}", self.id);
+        let action = match self.item_type {
+            MenuItemType::Check => gio::SimpleAction::new_stateful(
+                &name,
+                None,
+                &self.checked.load(Ordering::Relaxed).to_variant(),
+            ),
+            _ => gio::SimpleAction::new(&name, None),
+        };
+        action.set_enabled(self.enabled);
+
+        let id = self.id;
+        match self.item_type {
+            MenuItemType::Submenu => {}
+            MenuItemType::Check => {
+                let checked = self.checked.clone();
+                let mixed = self.mixed.clone();
+                let is_syncing_checked_state = self.is_syncing_checked_state.clone();
+                let store = self.gtk_menu_items.clone();
+                let gio_actions = self.gio_actions.clone();
+                action.connect_activate(move |action, _| {
+                    let c = !action
+                        .state()
+                        .and_then(|s| s.get::<bool>())
+                        .unwrap_or_default();
+                    checked.store(c, Ordering::Release);
+                    mixed.store(false, Ordering::Release);
+
+                    is_syncing_checked_state.store(true, Ordering::Release);
+                    for items in store.borrow().values() {
+                        for i in items {
+                            let i = i.downcast_ref::<gtk::CheckMenuItem>().unwrap();
+                            i.set_inconsistent(false);
+                            i.set_active(c);
+                        }
+                    }
+                    is_syncing_checked_state.store(false, Ordering::Release);
+
+                    for (_, action) in gio_actions.borrow().iter() {
+                        action.set_state(&c.to_variant());
+                    }
+
+                    MenuEvent::send(crate::MenuEvent { id });
+                });
+            }
+            MenuItemType::Predefined => {
+                let predefined_item_type = self.predefined_item_type.clone();
+                action.connect_activate(move |_, _| match &predefined_item_type {
+                    PredefinedMenuItemType::About(Some(metadata)) => show_about_dialog(metadata),
+                    PredefinedMenuItemType::About(None) => {}
+                    // TODO: wayland
+                    #[cfg(feature = "libxdo")]
+                    _ => {
+                        if let Ok(xdo) = libxdo::XDo::new(None) {
+                            let _ = xdo.send_keysequence(predefined_item_type.xdo_keys(), 0);
+                        }
+                    }
+                    #[cfg(not(feature = "libxdo"))]
+                    _ => {}
+                });
+            }
+            MenuItemType::MenuItem | MenuItemType::Icon => {
+                action.connect_activate(move |_, _| {
+                    MenuEvent::send(crate::MenuEvent { id });
+                });
+            }
+        }
+
+        app.add_action(&action);
+        if !self.has_display_only_accelerator() {
+            set_accels_for_action(app, &action, self.gio_accel_name()?);
+        }
+
+        self.gio_actions
+            .borrow_mut()
+            .push((app.clone(), action.clone()));
+
+        Ok(action)
+    }
+
+    /// Predefined edit items only display their accelerator, registering it would
+    /// swallow the key presses that `libxdo` sends on activation.
+    fn has_display_only_accelerator(&self) -> bool {
+        matches!(
+            self.predefined_item_type,
+            PredefinedMenuItemType::Copy
+                | PredefinedMenuItemType::Cut
+                | PredefinedMenuItemType::Paste
+                | PredefinedMenuItemType::SelectAll
+        )
+    }
+
+    fn gio_accel_name(&self) -> crate::Result<Option<String>> {
+        Ok(self
+            .accelerator
+            .as_ref()
+            .map(parse_accelerator)
+            .transpose()?
+            .and_then(|(mods, key)| gtk::accelerator_name(key, mods))
+            .map(|accel| accel.to_string()))
+    }
+}
+
+/// Updates `gio_menu` in place to show `items`, starting a new section for every separator.
+///
+/// Only the sections and items that changed are touched, so that menus opened from
+/// `gio_menu` aren't closed by unrelated changes.
+fn sync_gio_menu(
+    gio_menu: &gio::Menu,
+    items: Vec<MenuItemKind>,
+    app: &gtk::Application,
+) -> crate::Result<()> {
+    let mut layout: Vec<Vec<MenuItemKind>> = vec![Vec::new()];
+    for item in items {
+        let child = item.child();
+        if let PredefinedMenuItemType::Separator = child.predefined_item_type {
+            layout.push(Vec::new());
+        } else if child.has_gio_item() {
+            drop(child);
+            layout.last_mut().unwrap().push(item);
+        }
+    }
+    layout.retain(|section| !section.is_empty());
+
+    let sections = (0..gio_menu.n_items())
+        .filter_map(|i| {
+            gio_menu
+                .item_link(i, &gio::MENU_LINK_SECTION)
+                .and_then(|s| s.downcast::<gio::Menu>().ok())
+        })
+        .collect::<Vec<_>>();
+
+    for (i, items) in layout.iter().enumerate() {
+        let section = match sections.get(i) {
+            Some(section) => section.clone(),
+            None => {
+                let section = gio::Menu::new();
+                gio_menu.append_section(None, &section);
+                section
+            }
+        };
+        sync_gio_section(&section, items, app)?;
+    }
+
+    for (i, section) in sections.iter().enumerate().skip(layout.len()).rev() {
+        section.remove_all();
+        gio_menu.remove(i as i32);
+    }
+
+    Ok(())
+}
+
+/// Updates `section` in place to show `items`, keeping the items already in it.
+fn sync_gio_section(
+    section: &gio::Menu,
+    items: &[MenuItemKind],
+    app: &gtk::Application,
+) -> crate::Result<()> {
+    for i in (0..section.n_items()).rev() {
+        let id = section
+            .item_attribute_value(i, MUDA_ID_ATTRIBUTE, None)
+            .and_then(|v| v.get::<u32>());
+        if !items.iter().any(|item| Some(item.id()) == id) {
+            section.remove(i);
+        }
+    }
+
+    for (position, item) in items.iter().enumerate() {
+        let position = position as i32;
+        let gio_item = match gio_item_position(section, item.id()) {
+            Some(p) if p == position => continue,
+            Some(p) => {
+                let gio_item = gio::MenuItem::from_model(section, p);
+                section.remove(p);
+                gio_item
+            }
+            None => {
+                let mut child = item.child_mut();
+                let Some(gio_item) = child.create_gio_item(app)? else {
+                    continue;
+                };
+                if !child.gio_sections.contains(section) {
+                    child.gio_sections.push(section.clone());
+                }
+                gio_item
+            }
+        };
+        section.insert_item(position, &gio_item);
+    }
+
+    Ok(())
+}
+
+/// Removes all items from `gio_menu`, emptying its sections too
+/// so that items no longer find themselves in them.
+fn clear_gio_menu(gio_menu: &gio::Menu) {
+    for i in 0..gio_menu.n_items() {
+        if let Some(section) = gio_menu
+            .item_link(i, &gio::MENU_LINK_SECTION)
+            .and_then(|s| s.downcast::<gio::Menu>().ok())
+        {
+            section.remove_all();
+        }
+    }
+    gio_menu.remove_all();
+}
+
+fn gio_item_position(section: &gio::Menu, id: u32) -> Option<i32> {
+    (0..section.n_items()).find(|i| {
+        section
+            .item_attribute_value(*i, MUDA_ID_ATTRIBUTE, None)
+            .and_then(|v| v.get::<u32>())
+            == Some(id)
+    })
+}
+
+fn set_accels_for_action(
+    app: &gtk::Application,
+    action: &gio::SimpleAction,
+    accel: Option<String>,
+) {
+    let accels = accel.as_deref().into_iter().collect::<Vec<_>>();
+    app.set_accels_for_action(&format!("app.{}", action.name()), &accels);
+}
+
+#[cfg(test)]
+fn gio_menu_labels(gio_menu: &gio::MenuModel) -> Vec<Vec<String>> {
+    (0..gio_menu.n_items())
+        .filter_map(|i| gio_menu.item_link(i, &gio::MENU_LINK_SECTION))
+        .map(|section| {
+            (0..section.n_items())
+                .filter_map(|i| {
+                    section
+                        .item_attribute_value(i, &gio::MENU_ATTRIBUTE_LABEL, None)
+                        .and_then(|v| v.get::<String>())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_gio_menu_model() {
+    use crate::{CheckMenuItem, MenuItem, PredefinedMenuItem, Submenu};
+
+    let app = gtk::Application::new(None, Default::default());
+    let menu = crate::Menu::new();
+    let open = MenuItem::new("&Open", true, None);
+    let separator = PredefinedMenuItem::separator();
+    let wrap = CheckMenuItem::new("Wrap", true, false, None);
+    menu.append_items(&[&open, &separator, &wrap]).unwrap();
+
+    let model = menu.gtk_menu_model_for_gtk_application(&app).unwrap();
+    assert_eq!(gio_menu_labels(&model), [["_Open"], ["Wrap"]]);
+    let sections = (0..model.n_items())
+        .map(|i| model.item_link(i, &gio::MENU_LINK_SECTION).unwrap())
+        .collect::<Vec<_>>();
+
+    // changes only touch the affected section
+    let save = MenuItem::new("Save", true, None);
+    menu.insert(&save, 1).unwrap();
+    assert_eq!(
+        gio_menu_labels(&model),
+        [vec!["_Open", "Save"], vec!["Wrap"]]
+    );
+    assert_eq!(
+        model.item_link(0, &gio::MENU_LINK_SECTION).unwrap(),
+        sections[0]
+    );
+    assert_eq!(
+        model.item_link(1, &gio::MENU_LINK_SECTION).unwrap(),
+        sections[1]
+    );
+    assert_eq!(sections[1].n_items(), 1);
+
+    wrap.set_text("Word Wrap");
+    wrap.set_checked(true);
+    open.set_enabled(false);
+    assert_eq!(
+        gio_menu_labels(&model),
+        [vec!["_Open", "Save"], vec!["Word Wrap"]]
+    );
+    let action = |id: u32| app.lookup_action(&format!("muda-{id}"));
+    assert_eq!(
+        action(wrap.id()).unwrap().state().unwrap().get::<bool>(),
+        Some(true)
+    );
+    assert!(!action(open.id()).unwrap().is_enabled());
+    assert!(action(save.id()).unwrap().is_enabled());
+
+    menu.remove(&separator).unwrap();
+    assert_eq!(gio_menu_labels(&model), [["_Open", "Save", "Word Wrap"]]);
+    assert_eq!(
+        model.item_link(0, &gio::MENU_LINK_SECTION).unwrap(),
+        sections[0]
+    );
+    menu.remove(&open).unwrap();
+    assert_eq!(gio_menu_labels(&model), [["Save", "Word Wrap"]]);
+    assert!(action(open.id()).is_none());
+
+    let recent =
+        Submenu::with_items("Recent", true, &[&MenuItem::new("a.txt", true, None)]).unwrap();
+    menu.prepend(&recent).unwrap();
+    recent.append(&MenuItem::new("b.txt", true, None)).unwrap();
+    assert_eq!(gio_menu_labels(&model), [["Recent", "Save", "Word Wrap"]]);
+    let submenu = sections[0].item_link(0, &gio::MENU_LINK_SUBMENU).unwrap();
+    assert_eq!(gio_menu_labels(&submenu), [["a.txt", "b.txt"]]);
+}
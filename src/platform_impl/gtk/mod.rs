@@ -4,15 +4,18 @@
 
 mod accelerator;
 mod icon;
+mod menu_model;
 
+#[cfg(feature = "x11-global-hotkeys")]
+pub(crate) use accelerator::accelerator_keysym;
 pub(crate) use icon::PlatformIcon;
 
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, AcceleratorSequence, DisplayStyle},
     icon::{Icon, NativeIcon},
     items::*,
     util::{AddOp, Counter},
-    IsMenuItem, MenuEvent, MenuItemKind, MenuItemType, Position,
+    AboutMetadata, IsMenuItem, MenuEvent, MenuItemKind, MenuItemType, Position,
 };
 use accelerator::{from_gtk_mnemonic, parse_accelerator, to_gtk_mnemonic};
 use gtk::{prelude::*, Orientation};
@@ -58,6 +61,7 @@ pub struct Menu {
     gtk_menubars: HashMap<u32, gtk::MenuBar>,
     accel_group: Option<gtk::AccelGroup>,
     gtk_menu: (u32, Option<gtk::Menu>), // dedicated menu for tray or context menus
+    gio_menus: Vec<(gtk::Application, gtk::gio::Menu)>,
 }
 
 impl Menu {
@@ -68,6 +72,7 @@ impl Menu {
             gtk_menubars: HashMap::new(),
             accel_group: None,
             gtk_menu: (COUNTER.next(), None),
+            gio_menus: Vec::new(),
         }
     }
 
@@ -105,7 +110,7 @@ impl Menu {
             AddOp::Insert(position) => self.children.insert(position, item.child()),
         }
 
-        Ok(())
+        self.sync_gio_menus()
     }
 
     fn add_menu_item_with_id(&self, item: &dyn crate::IsMenuItem, id: u32) -> crate::Result<()> {
@@ -134,7 +139,10 @@ impl Menu {
     }
 
     pub fn remove(&mut self, item: &dyn crate::IsMenuItem) -> crate::Result<()> {
-        self.remove_inner(item, true, None)
+        self.remove_inner(item, true, None)?;
+        self.sync_gio_menus()?;
+        item.child().borrow_mut().remove_unused_gio_items();
+        Ok(())
     }
 
     pub fn remove_inner(
@@ -318,6 +326,49 @@ impl Menu {
         self.gtk_menubars.get(&(window.as_ptr() as u32)).cloned()
     }
 
+    pub fn reveal_item_for_gtk_window<W>(
+        &self,
+        window: &W,
+        item: &dyn crate::IsMenuItem,
+    ) -> crate::Result<()>
+    where
+        W: IsA<gtk::ApplicationWindow>,
+    {
+        let menu_bar = self
+            .gtk_menubars
+            .get(&(window.as_ptr() as u32))
+            .ok_or(crate::Error::NotInitialized)?;
+        let path = crate::menu::find_item_path(self.items(), item.id())
+            .ok_or(crate::Error::NotAChildOfThisMenu)?;
+
+        let mut shell = menu_bar.clone().upcast::<gtk::MenuShell>();
+        for (i, item) in path.iter().enumerate() {
+            // items of submenus are cached under the id of their submenu instead of
+            // the id of the window, so look for the one that is a child of `shell`
+            let gtk_item = item
+                .child()
+                .gtk_menu_items
+                .borrow()
+                .values()
+                .flatten()
+                .find(|i| i.parent().as_ref() == Some(shell.upcast_ref()))
+                .cloned()
+                .ok_or(crate::Error::NotInitialized)?;
+
+            shell.select_item(&gtk_item);
+            if i + 1 < path.len() {
+                // activating an item with a submenu pops the submenu up
+                gtk_item.activate();
+                shell = gtk_item
+                    .submenu()
+                    .and_then(|m| m.downcast::<gtk::MenuShell>().ok())
+                    .ok_or(crate::Error::NotInitialized)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn show_context_menu_for_gtk_window(
         &mut self,
         widget: &impl IsA<gtk::Widget>,
@@ -359,23 +410,38 @@ pub struct MenuChild {
 
     // menu item fields
     accelerator: Option<Accelerator>,
-    gtk_accelerator: Option<(gdk::ModifierType, u32)>,
+    /// accelerators registered in addition to `accelerator` but not displayed
+    alternate_accelerators: Vec<Accelerator>,
+    gtk_accelerators: Vec<(gdk::ModifierType, u32)>,
+    /// a multi-key sequence, only displayed as gtk can't handle it
+    accelerator_sequence: Option<AcceleratorSequence>,
 
     // predefined menu item fields
     predefined_item_type: PredefinedMenuItemType,
 
     // check menu item fields
     checked: Rc<AtomicBool>,
+    mixed: Rc<AtomicBool>,
     is_syncing_checked_state: Rc<AtomicBool>,
 
     // icon menu item fields
-    icon: Option<Icon>,
+    icon: Rc<RefCell<Option<Icon>>>,
+    icon_name: Option<String>,
 
     // submenu fields
     pub children: Option<Vec<Rc<RefCell<MenuChild>>>>,
     gtk_menus: HashMap<u32, Vec<(u32, gtk::Menu)>>,
     gtk_menu: (u32, Option<gtk::Menu>), // dedicated menu for tray or context menus
     accel_group: Option<gtk::AccelGroup>,
+
+    // gio menu model fields
+    gio_sections: Vec<gtk::gio::Menu>,
+    gio_actions: Rc<RefCell<Vec<(gtk::Application, gtk::gio::SimpleAction)>>>,
+    gio_menus: Vec<(gtk::Application, gtk::gio::Menu)>,
+
+    // global hotkey fields
+    #[cfg(feature = "x11-global-hotkeys")]
+    pub global_hotkey: super::x11_hotkeys::HotkeyGuard,
 }
 
 /// Constructors
@@ -447,7 +513,7 @@ impl MenuChild {
         Self {
             text: text.to_string(),
             enabled,
-            icon,
+            icon: Rc::new(RefCell::new(icon)),
             accelerator,
             id: COUNTER.next(),
             item_type: MenuItemType::Icon,
@@ -460,12 +526,13 @@ impl MenuChild {
     pub fn new_native_icon(
         text: &str,
         enabled: bool,
-        _native_icon: Option<NativeIcon>,
+        native_icon: Option<NativeIcon>,
         accelerator: Option<Accelerator>,
     ) -> Self {
         Self {
             text: text.to_string(),
             enabled,
+            icon_name: native_icon.map(|i| i.to_freedesktop_name().to_string()),
             accelerator,
             id: COUNTER.next(),
             item_type: MenuItemType::Icon,
@@ -506,9 +573,19 @@ impl MenuChild {
         let text = to_gtk_mnemonic(text);
         for items in self.gtk_menu_items.borrow().values() {
             for i in items {
-                i.set_label(&text);
+                match i.child().and_then(|c| c.downcast::<gtk::Box>().ok()) {
+                    Some(box_container) => {
+                        for child in box_container.children() {
+                            if let Some(label) = child.downcast_ref::<gtk::AccelLabel>() {
+                                label.set_label(&text);
+                            }
+                        }
+                    }
+                    None => i.set_label(&text),
+                }
             }
         }
+        self.update_gio_items(|i| i.set_label(Some(&text)));
     }
 
     pub fn is_enabled(&self) -> bool {
@@ -533,37 +610,116 @@ impl MenuChild {
                 i.set_sensitive(enabled);
             }
         }
+        for (_, action) in self.gio_actions.borrow().iter() {
+            action.set_enabled(enabled);
+        }
+    }
+
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.accelerator
+            .into_iter()
+            .chain(self.alternate_accelerators.iter().copied())
+            .collect()
     }
 
     pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
-        let prev_accel = self.gtk_accelerator.as_ref();
-        let new_accel = accelerator.as_ref().map(parse_accelerator).transpose()?;
+        self.set_accelerators(accelerator.into_iter().collect())
+    }
 
-        for items in self.gtk_menu_items.borrow().values() {
-            for i in items {
-                if let Some((mods, key)) = prev_accel {
-                    if let Some(accel_group) = &self.accel_group {
+    pub fn set_accelerators(&mut self, accelerators: Vec<Accelerator>) -> crate::Result<()> {
+        let new_accels = accelerators
+            .iter()
+            .map(parse_accelerator)
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        if let Some(accel_group) = &self.accel_group {
+            for items in self.gtk_menu_items.borrow().values() {
+                for i in items {
+                    for (mods, key) in &self.gtk_accelerators {
                         i.remove_accelerator(accel_group, *key, *mods);
                     }
+                    add_gtk_accelerators(i, accel_group, &new_accels);
                 }
-                if let Some((mods, key)) = new_accel {
-                    if let Some(accel_group) = &self.accel_group {
-                        i.add_accelerator(
-                            "activate",
-                            accel_group,
-                            key,
-                            mods,
-                            gtk::AccelFlags::VISIBLE,
-                        )
+            }
+        }
+
+        self.gtk_accelerators = new_accels;
+        let mut accelerators = accelerators.into_iter();
+        self.accelerator = accelerators.next();
+        self.alternate_accelerators = accelerators.collect();
+
+        if self.accelerator_sequence.take().is_some() {
+            for items in self.gtk_menu_items.borrow().values() {
+                for i in items {
+                    remove_gtk_sequence_label(i);
+                }
+            }
+        }
+
+        self.set_gio_accelerator()
+    }
+
+    pub fn set_accelerator_sequence(
+        &mut self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        match sequence {
+            Some(sequence) if sequence.accelerators().len() == 1 => {
+                self.set_accelerator(Some(sequence.accelerators()[0]))
+            }
+            sequence => {
+                self.set_accelerator(None)?;
+                self.accelerator_sequence = sequence;
+                for items in self.gtk_menu_items.borrow().values() {
+                    for i in items {
+                        self.add_gtk_sequence_label(i);
                     }
                 }
+                Ok(())
             }
         }
+    }
 
-        self.gtk_accelerator = new_accel;
-        self.accelerator = accelerator;
+    /// Shows the multi-key accelerator sequence, if any, at the end of `item`
+    /// the same way gtk shows accelerators.
+    fn add_gtk_sequence_label(&self, item: &gtk::MenuItem) {
+        let Some(sequence) = &self.accelerator_sequence else {
+            return;
+        };
 
-        Ok(())
+        let box_container = match item.child().and_then(|c| c.downcast::<gtk::Box>().ok()) {
+            Some(box_container) => {
+                remove_gtk_sequence_label(item);
+                box_container
+            }
+            None => {
+                let label = gtk::AccelLabel::builder()
+                    .label(&to_gtk_mnemonic(&self.text))
+                    .use_underline(true)
+                    .xalign(0.0)
+                    .build();
+                label.set_accel_widget(Some(item));
+
+                let box_container = gtk::Box::new(Orientation::Horizontal, 6);
+                box_container.pack_start(&label, true, true, 0);
+                box_container.show_all();
+
+                if let Some(child) = item.child() {
+                    item.remove(&child);
+                }
+                item.add(&box_container);
+                box_container
+            }
+        };
+
+        let label = gtk::Label::new(Some(&sequence.to_display_string(DisplayStyle::Gnome)));
+        label.style_context().add_class("accelerator");
+        box_container.pack_end(&label, false, false, 0);
+        label.show();
     }
 }
 
@@ -586,34 +742,158 @@ impl MenuChild {
 
     pub fn set_checked(&mut self, checked: bool) {
         self.checked.store(checked, Ordering::Release);
+        self.mixed.store(false, Ordering::Release);
         self.is_syncing_checked_state.store(true, Ordering::Release);
         for items in self.gtk_menu_items.borrow().values() {
             for i in items {
-                i.downcast_ref::<gtk::CheckMenuItem>()
-                    .unwrap()
-                    .set_active(checked);
+                let i = i.downcast_ref::<gtk::CheckMenuItem>().unwrap();
+                i.set_inconsistent(false);
+                i.set_active(checked);
             }
         }
         self.is_syncing_checked_state
             .store(false, Ordering::Release);
+        for (_, action) in self.gio_actions.borrow().iter() {
+            action.set_state(&checked.to_variant());
+        }
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.mixed.load(Ordering::Relaxed)
+    }
+
+    pub fn set_mixed(&mut self, mixed: bool) {
+        self.mixed.store(mixed, Ordering::Release);
+        for items in self.gtk_menu_items.borrow().values() {
+            for i in items {
+                i.downcast_ref::<gtk::CheckMenuItem>()
+                    .unwrap()
+                    .set_inconsistent(mixed);
+            }
+        }
     }
 }
 
-/// IconMenuItem methods
+/// IconMenuItem, CheckMenuItem and Submenu methods
 impl MenuChild {
     pub fn set_icon(&mut self, icon: Option<Icon>) {
-        self.icon = icon.clone();
+        *self.icon.borrow_mut() = icon;
+        self.icon_name = None;
+        self.update_icons();
+    }
 
-        let pixbuf = icon.map(|i| i.inner.to_pixbuf_scale(16, 16));
+    pub fn set_icon_name(&mut self, icon_name: Option<&str>) {
+        self.icon_name = icon_name.map(|n| n.to_string());
+        *self.icon.borrow_mut() = None;
+        self.update_icons();
+    }
+
+    fn update_icons(&mut self) {
         for items in self.gtk_menu_items.borrow().values() {
             for i in items {
-                let box_container = i.child().unwrap().downcast::<gtk::Box>().unwrap();
-                box_container.children()[0]
-                    .downcast_ref::<gtk::Image>()
-                    .unwrap()
-                    .set_pixbuf(pixbuf.as_ref())
+                match gtk_item_box(i) {
+                    Some(box_container) => {
+                        self.update_gtk_image(box_container.children()[0].downcast_ref().unwrap())
+                    }
+                    // check items and submenus only get an image once they have an icon
+                    None if self.has_icon() => self.add_gtk_item_box(i),
+                    None => {}
+                }
             }
         }
+        self.set_gio_icon();
+    }
+
+    fn has_icon(&self) -> bool {
+        self.icon.borrow().is_some() || self.icon_name.is_some()
+    }
+
+    /// Sets the child of `item` to a box with an image followed by the label,
+    /// replacing its plain label if any.
+    fn add_gtk_item_box(&self, item: &gtk::MenuItem) {
+        let image = self.create_gtk_image();
+
+        let label = gtk::AccelLabel::builder()
+            .label(&to_gtk_mnemonic(&self.text))
+            .use_underline(true)
+            .xalign(0.0)
+            .build();
+        label.set_accel_widget(Some(item));
+
+        let box_container = gtk::Box::new(Orientation::Horizontal, 6);
+        // move the image into the space reserved for check marks,
+        // which check items need for their own
+        if self.item_type != MenuItemType::Check {
+            let style_context = box_container.style_context();
+            let css_provider = gtk::CssProvider::new();
+            let theme = r#"
+                box {
+                    margin-left: -22px;
+                }
+              "#;
+            let _ = css_provider.load_from_data(theme.as_bytes());
+            style_context.add_provider(&css_provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION);
+        }
+        box_container.pack_start(&image, false, false, 0);
+        box_container.pack_start(&label, true, true, 0);
+        box_container.show_all();
+
+        if let Some(child) = item.child() {
+            item.remove(&child);
+        }
+        item.add(&box_container);
+
+        self.add_gtk_sequence_label(item);
+    }
+
+    fn update_gtk_image(&self, image: &gtk::Image) {
+        match (&*self.icon.borrow(), &self.icon_name) {
+            (Some(icon), _) => set_gtk_image_icon(image, icon),
+            (None, Some(icon_name)) => {
+                image.set_from_icon_name(Some(icon_name), gtk::IconSize::Menu)
+            }
+            (None, None) => image.clear(),
+        }
+    }
+
+    fn create_gtk_image(&self) -> gtk::Image {
+        let image = gtk::Image::default();
+        self.update_gtk_image(&image);
+
+        // themed icons are re-rendered by gtk itself when the scale factor or theme changes,
+        // but surfaces have to be recreated
+        let icon = self.icon.clone();
+        image.connect_scale_factor_notify(move |image| {
+            if let Some(icon) = &*icon.borrow() {
+                set_gtk_image_icon(image, icon);
+            }
+        });
+
+        if let Some(settings) = gtk::Settings::default() {
+            // not part of the prelude because of `gio::prelude::SettingsExt`
+            use gtk::traits::SettingsExt;
+
+            let handlers = [
+                settings.connect_gtk_theme_name_notify({
+                    let image = image.downgrade();
+                    let icon = self.icon.clone();
+                    move |_| update_gtk_image_icon_when_idle(&image, &icon)
+                }),
+                settings.connect_gtk_application_prefer_dark_theme_notify({
+                    let image = image.downgrade();
+                    let icon = self.icon.clone();
+                    move |_| update_gtk_image_icon_when_idle(&image, &icon)
+                }),
+            ];
+            let handlers = RefCell::new(Some(handlers));
+            image.connect_destroy(move |_| {
+                for handler in handlers.take().into_iter().flatten() {
+                    settings.disconnect(handler);
+                }
+            });
+        }
+
+        image
     }
 }
 
@@ -656,7 +936,7 @@ impl MenuChild {
                 .insert(position, item.child()),
         }
 
-        Ok(())
+        self.sync_gio_menus()
     }
 
     fn add_menu_item_with_id(&self, item: &dyn crate::IsMenuItem, id: u32) -> crate::Result<()> {
@@ -688,7 +968,10 @@ impl MenuChild {
     }
 
     pub fn remove(&mut self, item: &dyn crate::IsMenuItem) -> crate::Result<()> {
-        self.remove_inner(item, true, None)
+        self.remove_inner(item, true, None)?;
+        self.sync_gio_menus()?;
+        item.child().borrow_mut().remove_unused_gio_items();
+        Ok(())
     }
 
     fn remove_inner(
@@ -797,26 +1080,34 @@ impl MenuChild {
 
 macro_rules! register_accel {
     ($self:ident, $item:ident, $accel_group:ident) => {
-        $self.gtk_accelerator = $self
-            .accelerator
-            .as_ref()
+        $self.gtk_accelerators = $self
+            .accelerators()
+            .iter()
             .map(parse_accelerator)
-            .transpose()?;
-
-        if let Some((mods, key)) = &$self.gtk_accelerator {
-            if let Some(accel_group) = $accel_group {
-                $item.add_accelerator(
-                    "activate",
-                    accel_group,
-                    *key,
-                    *mods,
-                    gtk::AccelFlags::VISIBLE,
-                )
-            }
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        if let Some(accel_group) = $accel_group {
+            add_gtk_accelerators(&$item, accel_group, &$self.gtk_accelerators);
         }
     };
 }
 
+/// Registers `accelerators` on `item`, only the first one is shown by its label.
+fn add_gtk_accelerators(
+    item: &impl IsA<gtk::Widget>,
+    accel_group: &gtk::AccelGroup,
+    accelerators: &[(gdk::ModifierType, u32)],
+) {
+    for (i, (mods, key)) in accelerators.iter().enumerate() {
+        let flags = if i == 0 {
+            gtk::AccelFlags::VISIBLE
+        } else {
+            gtk::AccelFlags::empty()
+        };
+        item.add_accelerator("activate", accel_group, *key, *mods, flags);
+    }
+}
+
 /// Gtk menu item creation methods
 impl MenuChild {
     fn create_gtk_item_for_submenu(
@@ -836,6 +1127,10 @@ impl MenuChild {
         item.show();
         item.set_submenu(Some(&submenu));
 
+        if self.has_icon() {
+            self.add_gtk_item_box(&item);
+        }
+
         self.accel_group = accel_group.cloned();
 
         let mut id = 0;
@@ -880,6 +1175,7 @@ impl MenuChild {
         self.accel_group = accel_group.cloned();
 
         register_accel!(self, item, accel_group);
+        self.add_gtk_sequence_label(&item);
 
         let id = self.id;
         item.connect_activate(move |_| {
@@ -904,11 +1200,11 @@ impl MenuChild {
         add_to_cache: bool,
     ) -> crate::Result<gtk::MenuItem> {
         let text = self.text.clone();
-        self.gtk_accelerator = self
-            .accelerator
-            .as_ref()
+        self.gtk_accelerators = self
+            .accelerators()
+            .iter()
             .map(parse_accelerator)
-            .transpose()?;
+            .collect::<crate::Result<Vec<_>>>()?;
         let predefined_item_type = self.predefined_item_type.clone();
 
         let make_item = || {
@@ -919,16 +1215,8 @@ impl MenuChild {
                 .build()
         };
         let register_accel = |item: &gtk::MenuItem| {
-            if let Some((mods, key)) = &self.gtk_accelerator {
-                if let Some(accel_group) = accel_group {
-                    item.add_accelerator(
-                        "activate",
-                        accel_group,
-                        *key,
-                        *mods,
-                        gtk::AccelFlags::VISIBLE,
-                    )
-                }
+            if let Some(accel_group) = accel_group {
+                add_gtk_accelerators(item, accel_group, &self.gtk_accelerators);
             }
         };
 
@@ -962,43 +1250,7 @@ impl MenuChild {
                 register_accel(&item);
                 item.connect_activate(move |_| {
                     if let Some(metadata) = &metadata {
-                        let mut builder = gtk::builders::AboutDialogBuilder::new()
-                            .modal(true)
-                            .resizable(false);
-
-                        if let Some(name) = &metadata.name {
-                            builder = builder.program_name(name);
-                        }
-                        if let Some(version) = &metadata.full_version() {
-                            builder = builder.version(version);
-                        }
-                        if let Some(authors) = &metadata.authors {
-                            builder = builder.authors(authors.clone());
-                        }
-                        if let Some(comments) = &metadata.comments {
-                            builder = builder.comments(comments);
-                        }
-                        if let Some(copyright) = &metadata.copyright {
-                            builder = builder.copyright(copyright);
-                        }
-                        if let Some(license) = &metadata.license {
-                            builder = builder.license(license);
-                        }
-                        if let Some(website) = &metadata.website {
-                            builder = builder.website(website);
-                        }
-                        if let Some(website_label) = &metadata.website_label {
-                            builder = builder.website_label(website_label);
-                        }
-                        if let Some(icon) = &metadata.icon {
-                            builder = builder.logo(&icon.inner.to_pixbuf());
-                        }
-
-                        let about = builder.build();
-                        about.run();
-                        unsafe {
-                            about.destroy();
-                        }
+                        show_about_dialog(metadata);
                     }
                 });
                 item
@@ -1027,16 +1279,24 @@ impl MenuChild {
             .use_underline(true)
             .sensitive(self.enabled)
             .active(self.checked.load(Ordering::Relaxed))
+            .inconsistent(self.mixed.load(Ordering::Relaxed))
             .build();
 
+        if self.has_icon() {
+            self.add_gtk_item_box(item.upcast_ref());
+        }
+
         self.accel_group = accel_group.cloned();
 
         register_accel!(self, item, accel_group);
+        self.add_gtk_sequence_label(item.upcast_ref());
 
         let id = self.id;
         let is_syncing_checked_state = self.is_syncing_checked_state.clone();
         let checked = self.checked.clone();
+        let mixed = self.mixed.clone();
         let store = self.gtk_menu_items.clone();
+        let gio_actions = self.gio_actions.clone();
         item.connect_toggled(move |i| {
             let should_dispatch = is_syncing_checked_state
                 .compare_exchange(false, true, Ordering::Release, Ordering::Relaxed)
@@ -1045,17 +1305,22 @@ impl MenuChild {
             if should_dispatch {
                 let c = i.is_active();
                 checked.store(c, Ordering::Release);
+                mixed.store(false, Ordering::Release);
 
                 for items in store.borrow().values() {
                     for i in items {
-                        i.downcast_ref::<gtk::CheckMenuItem>()
-                            .unwrap()
-                            .set_active(c);
+                        let i = i.downcast_ref::<gtk::CheckMenuItem>().unwrap();
+                        i.set_inconsistent(false);
+                        i.set_active(c);
                     }
                 }
 
                 is_syncing_checked_state.store(false, Ordering::Release);
 
+                for (_, action) in gio_actions.borrow().iter() {
+                    action.set_state(&c.to_variant());
+                }
+
                 MenuEvent::send(crate::MenuEvent { id });
             }
         });
@@ -1079,40 +1344,13 @@ impl MenuChild {
         accel_group: Option<&gtk::AccelGroup>,
         add_to_cache: bool,
     ) -> crate::Result<gtk::MenuItem> {
-        let image = self
-            .icon
-            .as_ref()
-            .map(|i| gtk::Image::from_pixbuf(Some(&i.inner.to_pixbuf_scale(16, 16))))
-            .unwrap_or_else(gtk::Image::default);
-
         self.accel_group = accel_group.cloned();
 
-        let label = gtk::AccelLabel::builder()
-            .label(&to_gtk_mnemonic(&self.text))
-            .use_underline(true)
-            .xalign(0.0)
-            .build();
-
-        let box_container = gtk::Box::new(Orientation::Horizontal, 6);
-        let style_context = box_container.style_context();
-        let css_provider = gtk::CssProvider::new();
-        let theme = r#"
-            box {
-                margin-left: -22px;
-            }
-          "#;
-        let _ = css_provider.load_from_data(theme.as_bytes());
-        style_context.add_provider(&css_provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION);
-        box_container.pack_start(&image, false, false, 0);
-        box_container.pack_start(&label, true, true, 0);
-        box_container.show_all();
-
-        let item = gtk::MenuItem::builder()
-            .child(&box_container)
-            .sensitive(self.enabled)
-            .build();
+        let item = gtk::MenuItem::builder().sensitive(self.enabled).build();
+        self.add_gtk_item_box(&item);
 
         register_accel!(self, item, accel_group);
+        self.add_gtk_sequence_label(&item);
 
         let id = self.id;
         item.connect_activate(move |_| {
@@ -1221,6 +1459,97 @@ fn show_context_menu(
     }
 }
 
+/// Returns the box holding the image and label of `item`, if it has an icon.
+fn gtk_item_box(item: &gtk::MenuItem) -> Option<gtk::Box> {
+    item.child()
+        .and_then(|c| c.downcast::<gtk::Box>().ok())
+        .filter(|b| b.children().first().is_some_and(|c| c.is::<gtk::Image>()))
+}
+
+/// Removes the label added by [`MenuChild::add_gtk_sequence_label`] from `item`, if any.
+fn remove_gtk_sequence_label(item: &gtk::MenuItem) {
+    if let Some(box_container) = item.child().and_then(|c| c.downcast::<gtk::Box>().ok()) {
+        for child in box_container.children() {
+            // the item text is the only other label, and is an `AccelLabel`
+            if child.is::<gtk::Label>() && !child.is::<gtk::AccelLabel>() {
+                box_container.remove(&child);
+            }
+        }
+    }
+}
+
+/// Renders `icon` at 16x16 logical pixels for the current scale factor and theme of `image`.
+fn set_gtk_image_icon(image: &gtk::Image, icon: &Icon) {
+    let color = image.style_context().color(image.state_flags());
+    // a light text color means a dark theme is in use
+    let is_dark = 0.2126 * color.red() + 0.7152 * color.green() + 0.0722 * color.blue() > 0.5;
+    let variant = match &icon.dark {
+        Some(dark) if is_dark => dark,
+        _ => &icon.inner,
+    };
+
+    let color =
+        [color.red(), color.green(), color.blue(), color.alpha()].map(|c| (c * 255.).round() as u8);
+    let surface =
+        variant.to_cairo_surface(16, image.scale_factor(), icon.is_template.then_some(color));
+    image.set_from_surface(surface.as_ref());
+}
+
+/// Re-renders the icon of `image` once gtk is done applying the theme change
+/// that triggered this, so the new theme colors are picked up.
+fn update_gtk_image_icon_when_idle(
+    image: &gtk::glib::WeakRef<gtk::Image>,
+    icon: &Rc<RefCell<Option<Icon>>>,
+) {
+    let image = image.clone();
+    let icon = icon.clone();
+    gtk::glib::idle_add_local_once(move || {
+        if let (Some(image), Some(icon)) = (image.upgrade(), &*icon.borrow()) {
+            set_gtk_image_icon(&image, icon);
+        }
+    });
+}
+
+fn show_about_dialog(metadata: &AboutMetadata) {
+    let mut builder = gtk::builders::AboutDialogBuilder::new()
+        .modal(true)
+        .resizable(false);
+
+    if let Some(name) = &metadata.name {
+        builder = builder.program_name(name);
+    }
+    if let Some(version) = &metadata.full_version() {
+        builder = builder.version(version);
+    }
+    if let Some(authors) = &metadata.authors {
+        builder = builder.authors(authors.clone());
+    }
+    if let Some(comments) = &metadata.comments {
+        builder = builder.comments(comments);
+    }
+    if let Some(copyright) = &metadata.copyright {
+        builder = builder.copyright(copyright);
+    }
+    if let Some(license) = &metadata.license {
+        builder = builder.license(license);
+    }
+    if let Some(website) = &metadata.website {
+        builder = builder.website(website);
+    }
+    if let Some(website_label) = &metadata.website_label {
+        builder = builder.website_label(website_label);
+    }
+    if let Some(icon) = &metadata.icon {
+        builder = builder.logo(&icon.inner.to_pixbuf());
+    }
+
+    let about = builder.build();
+    about.run();
+    unsafe {
+        about.destroy();
+    }
+}
+
 impl PredefinedMenuItemType {
     #[cfg(feature = "libxdo")]
     fn xdo_keys(&self) -> &str {
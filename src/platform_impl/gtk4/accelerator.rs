@@ -0,0 +1,318 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use gtk4::gdk;
+use keyboard_types::{Code, Modifiers};
+
+use crate::accelerator::Accelerator;
+
+pub fn to_gtk_mnemonic<S: AsRef<str>>(string: S) -> String {
+    string
+        .as_ref()
+        .replace("&&", "[~~]")
+        .replace('&', "_")
+        .replace("[~~]", "&&")
+        .replace("[~~]", "&")
+}
+
+pub fn parse_accelerator(
+    accelerator: &Accelerator,
+) -> crate::Result<(gdk::ModifierType, gdk::Key)> {
+    // gtk4 shortcuts have no modifier for AltGr, they would trigger without it
+    if accelerator.mods.contains(Modifiers::ALT_GRAPH) {
+        return Err(crate::Error::UnsupportedAcceleratorModifiers(
+            Modifiers::ALT_GRAPH,
+        ));
+    }
+
+    Ok((
+        modifiers_to_gdk_modifier_type(accelerator.mods),
+        accelerator_key(accelerator)?,
+    ))
+}
+
+fn accelerator_key(accelerator: &Accelerator) -> crate::Result<gdk::Key> {
+    // gtk matches keyvals through the current keymap, so a logical key
+    // only needs the keyval of its character.
+    if let Some(c) = accelerator.logical {
+        // `gdk::unicode_to_keyval` requires gtk to be initialized, the ffi function doesn't.
+        let key: gdk::Key =
+            unsafe { gdk::glib::translate::from_glib(gdk::ffi::gdk_unicode_to_keyval(c as u32)) };
+        return Ok(key.to_lower());
+    }
+
+    key_to_raw_key(&accelerator.key)
+        .ok_or_else(|| crate::Error::UnrecognizedAcceleratorCode(accelerator.key.to_string()))
+}
+
+/// Formats an accelerator the way `gtk_accelerator_name` does, which,
+/// unlike the latter, doesn't require gtk to be initialized.
+pub fn to_gtk_accelerator_name(accelerator: &Accelerator) -> crate::Result<String> {
+    let (mods, key) = parse_accelerator(accelerator)?;
+
+    let mut name = String::new();
+    if mods.contains(gdk::ModifierType::SHIFT_MASK) {
+        name.push_str("<Shift>");
+    }
+    if mods.contains(gdk::ModifierType::CONTROL_MASK) {
+        name.push_str("<Control>");
+    }
+    if mods.contains(gdk::ModifierType::ALT_MASK) {
+        name.push_str("<Alt>");
+    }
+    if mods.contains(gdk::ModifierType::SUPER_MASK) {
+        name.push_str("<Super>");
+    }
+    if mods.contains(gdk::ModifierType::HYPER_MASK) {
+        name.push_str("<Hyper>");
+    }
+    if mods.contains(gdk::ModifierType::META_MASK) {
+        name.push_str("<Meta>");
+    }
+    if let Some(key_name) = key.name() {
+        name.push_str(&key_name);
+    }
+
+    Ok(name)
+}
+
+/// Returns the keyval of `accelerator`, which is also its X11 keysym.
+#[cfg(feature = "x11-global-hotkeys")]
+pub fn accelerator_keysym(accelerator: &Accelerator) -> crate::Result<u32> {
+    use gdk::glib::translate::IntoGlib;
+    // the X11 hotkeys support AltGr, so only the key is parsed
+    accelerator_key(accelerator).map(|key| key.into_glib())
+}
+
+fn modifiers_to_gdk_modifier_type(modifiers: Modifiers) -> gdk::ModifierType {
+    let mut result = gdk::ModifierType::empty();
+
+    result.set(
+        gdk::ModifierType::ALT_MASK,
+        modifiers.contains(Modifiers::ALT),
+    );
+    result.set(
+        gdk::ModifierType::CONTROL_MASK,
+        modifiers.contains(Modifiers::CONTROL),
+    );
+    result.set(
+        gdk::ModifierType::SHIFT_MASK,
+        modifiers.contains(Modifiers::SHIFT),
+    );
+    result.set(
+        gdk::ModifierType::SUPER_MASK,
+        modifiers.contains(Modifiers::SUPER),
+    );
+    result.set(
+        gdk::ModifierType::HYPER_MASK,
+        modifiers.contains(Modifiers::HYPER),
+    );
+    result.set(
+        gdk::ModifierType::META_MASK,
+        modifiers.contains(Modifiers::META),
+    );
+
+    result
+}
+
+fn key_to_raw_key(src: &Code) -> Option<gdk::Key> {
+    use gdk::Key;
+    Some(match src {
+        Code::KeyA => Key::a,
+        Code::KeyB => Key::b,
+        Code::KeyC => Key::c,
+        Code::KeyD => Key::d,
+        Code::KeyE => Key::e,
+        Code::KeyF => Key::f,
+        Code::KeyG => Key::g,
+        Code::KeyH => Key::h,
+        Code::KeyI => Key::i,
+        Code::KeyJ => Key::j,
+        Code::KeyK => Key::k,
+        Code::KeyL => Key::l,
+        Code::KeyM => Key::m,
+        Code::KeyN => Key::n,
+        Code::KeyO => Key::o,
+        Code::KeyP => Key::p,
+        Code::KeyQ => Key::q,
+        Code::KeyR => Key::r,
+        Code::KeyS => Key::s,
+        Code::KeyT => Key::t,
+        Code::KeyU => Key::u,
+        Code::KeyV => Key::v,
+        Code::KeyW => Key::w,
+        Code::KeyX => Key::x,
+        Code::KeyY => Key::y,
+        Code::KeyZ => Key::z,
+        Code::Digit0 => Key::_0,
+        Code::Digit1 => Key::_1,
+        Code::Digit2 => Key::_2,
+        Code::Digit3 => Key::_3,
+        Code::Digit4 => Key::_4,
+        Code::Digit5 => Key::_5,
+        Code::Digit6 => Key::_6,
+        Code::Digit7 => Key::_7,
+        Code::Digit8 => Key::_8,
+        Code::Digit9 => Key::_9,
+        Code::Comma => Key::comma,
+        Code::Minus => Key::minus,
+        Code::Period => Key::period,
+        Code::Space => Key::space,
+        Code::Equal => Key::equal,
+        Code::Semicolon => Key::semicolon,
+        Code::Slash => Key::slash,
+        Code::Backslash => Key::backslash,
+        Code::Quote => Key::apostrophe,
+        Code::Backquote => Key::grave,
+        Code::BracketLeft => Key::bracketleft,
+        Code::BracketRight => Key::bracketright,
+
+        Code::Escape => Key::Escape,
+        Code::Backspace => Key::BackSpace,
+
+        Code::Tab => Key::Tab,
+        Code::Enter => Key::Return,
+
+        Code::ControlLeft => Key::Control_L,
+        Code::AltLeft => Key::Alt_L,
+        Code::ShiftLeft => Key::Shift_L,
+        Code::MetaLeft => Key::Super_L,
+
+        Code::ControlRight => Key::Control_R,
+        Code::AltRight => Key::Alt_R,
+        Code::ShiftRight => Key::Shift_R,
+        Code::MetaRight => Key::Super_R,
+
+        Code::CapsLock => Key::Caps_Lock,
+        Code::F1 => Key::F1,
+        Code::F2 => Key::F2,
+        Code::F3 => Key::F3,
+        Code::F4 => Key::F4,
+        Code::F5 => Key::F5,
+        Code::F6 => Key::F6,
+        Code::F7 => Key::F7,
+        Code::F8 => Key::F8,
+        Code::F9 => Key::F9,
+        Code::F10 => Key::F10,
+        Code::F11 => Key::F11,
+        Code::F12 => Key::F12,
+        Code::F13 => Key::F13,
+        Code::F14 => Key::F14,
+        Code::F15 => Key::F15,
+        Code::F16 => Key::F16,
+        Code::F17 => Key::F17,
+        Code::F18 => Key::F18,
+        Code::F19 => Key::F19,
+        Code::F20 => Key::F20,
+        Code::F21 => Key::F21,
+        Code::F22 => Key::F22,
+        Code::F23 => Key::F23,
+        Code::F24 => Key::F24,
+
+        Code::PrintScreen => Key::Print,
+        Code::ScrollLock => Key::Scroll_Lock,
+        // Pause/Break not audio.
+        Code::Pause => Key::Pause,
+
+        Code::Insert => Key::Insert,
+        Code::Delete => Key::Delete,
+        Code::Home => Key::Home,
+        Code::End => Key::End,
+        Code::PageUp => Key::Page_Up,
+        Code::PageDown => Key::Page_Down,
+
+        Code::NumLock => Key::Num_Lock,
+
+        Code::ArrowUp => Key::Up,
+        Code::ArrowDown => Key::Down,
+        Code::ArrowLeft => Key::Left,
+        Code::ArrowRight => Key::Right,
+
+        Code::Numpad0 => Key::KP_0,
+        Code::Numpad1 => Key::KP_1,
+        Code::Numpad2 => Key::KP_2,
+        Code::Numpad3 => Key::KP_3,
+        Code::Numpad4 => Key::KP_4,
+        Code::Numpad5 => Key::KP_5,
+        Code::Numpad6 => Key::KP_6,
+        Code::Numpad7 => Key::KP_7,
+        Code::Numpad8 => Key::KP_8,
+        Code::Numpad9 => Key::KP_9,
+        Code::NumpadAdd => Key::KP_Add,
+        Code::NumpadComma => Key::KP_Separator,
+        Code::NumpadDecimal => Key::KP_Decimal,
+        Code::NumpadDivide => Key::KP_Divide,
+        Code::NumpadEnter => Key::KP_Enter,
+        Code::NumpadEqual => Key::KP_Equal,
+        Code::NumpadMultiply => Key::KP_Multiply,
+        Code::NumpadSubtract => Key::KP_Subtract,
+
+        Code::ContextMenu => Key::Menu,
+        Code::Help => Key::Help,
+        Code::WakeUp => Key::WakeUp,
+
+        Code::AudioVolumeDown => Key::AudioLowerVolume,
+        Code::AudioVolumeUp => Key::AudioRaiseVolume,
+        Code::AudioVolumeMute => Key::AudioMute,
+        Code::MediaPlayPause => Key::AudioPlay,
+        Code::MediaStop => Key::AudioStop,
+        Code::MediaTrackNext => Key::AudioNext,
+        Code::MediaTrackPrevious => Key::AudioPrev,
+        Code::MediaSelect => Key::AudioMedia,
+
+        Code::BrowserBack => Key::Back,
+        Code::BrowserForward => Key::Forward,
+        Code::BrowserRefresh => Key::Refresh,
+        Code::BrowserStop => Key::Stop,
+        Code::BrowserSearch => Key::Search,
+        Code::BrowserFavorites => Key::Favorites,
+        Code::BrowserHome => Key::HomePage,
+        Code::LaunchMail => Key::Mail,
+        Code::LaunchApp1 => Key::MyComputer,
+        Code::LaunchApp2 => Key::Calculator,
+
+        // The keysyms of these keys on the layouts they are found on.
+        Code::IntlBackslash => Key::less,
+        Code::IntlRo => Key::backslash,
+        Code::IntlYen => Key::yen,
+        Code::Convert => Key::Henkan,
+        Code::NonConvert => Key::Muhenkan,
+        Code::KanaMode => Key::Hiragana_Katakana,
+        Code::Lang1 => Key::Hangul,
+        Code::Lang2 => Key::Hangul_Hanja,
+        Code::Lang3 => Key::Katakana,
+        Code::Lang4 => Key::Hiragana,
+        Code::Lang5 => Key::Zenkaku_Hankaku,
+        _ => return None,
+    })
+}
+
+#[test]
+fn test_parse_accelerator_codes() {
+    for code in crate::accelerator::PARSEABLE_CODES {
+        let accelerator = Accelerator::new(None, *code);
+        assert!(
+            parse_accelerator(&accelerator).is_ok(),
+            "{code} isn't mapped"
+        );
+    }
+}
+
+#[test]
+fn test_extra_modifiers() {
+    let accelerator = Accelerator::new(Some(Modifiers::CONTROL), Code::KeyQ)
+        .with_modifiers(Modifiers::HYPER | Modifiers::META);
+    assert_eq!(
+        to_gtk_accelerator_name(&accelerator).unwrap(),
+        "<Control><Hyper><Meta>q"
+    );
+
+    let accelerator = Accelerator::new(None, Code::KeyQ).with_modifiers(Modifiers::ALT_GRAPH);
+    assert!(matches!(
+        parse_accelerator(&accelerator),
+        Err(crate::Error::UnsupportedAcceleratorModifiers(
+            Modifiers::ALT_GRAPH
+        ))
+    ));
+}
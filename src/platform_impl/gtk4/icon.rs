@@ -0,0 +1,142 @@
+// Copyright 2014-2021 The winit contributors
+// Copyright 2021-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+
+use gtk4::gdk_pixbuf::{self, Colorspace, Pixbuf};
+
+use crate::icon::BadIcon;
+#[cfg(feature = "svg")]
+use crate::icon::SvgIcon;
+
+#[derive(Debug, Clone)]
+struct RawImage {
+    raw: Vec<u8>,
+    width: i32,
+    height: i32,
+    row_stride: i32,
+}
+
+impl RawImage {
+    fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        let row_stride =
+            Pixbuf::calculate_rowstride(Colorspace::Rgb, true, 8, width as i32, height as i32);
+        Self {
+            raw: rgba,
+            width: width as i32,
+            height: height as i32,
+            row_stride,
+        }
+    }
+
+    fn to_pixbuf(&self) -> Pixbuf {
+        Pixbuf::from_mut_slice(
+            self.raw.clone(),
+            gdk_pixbuf::Colorspace::Rgb,
+            true,
+            8,
+            self.width,
+            self.height,
+            self.row_stride,
+        )
+    }
+}
+
+/// An icon used for the window titlebar, taskbar, etc.
+///
+/// Holds one or more resolutions of the same image, sorted from the smallest to the largest.
+#[derive(Debug, Clone)]
+pub struct PlatformIcon {
+    images: Vec<RawImage>,
+    #[cfg(feature = "svg")]
+    svg: Option<SvgIcon>,
+}
+
+impl From<PlatformIcon> for Pixbuf {
+    fn from(icon: PlatformIcon) -> Self {
+        icon.to_pixbuf()
+    }
+}
+
+impl PlatformIcon {
+    /// Creates an `Icon` from 32bpp RGBA data.
+    ///
+    /// The length of `rgba` must be divisible by 4, and `width * height` must equal
+    /// `rgba.len() / 4`. Otherwise, this will return a `BadIcon` error.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        Ok(Self {
+            images: vec![RawImage::from_rgba(rgba, width, height)],
+            #[cfg(feature = "svg")]
+            svg: None,
+        })
+    }
+
+    /// Creates an `Icon` from an SVG image, rasterized again at every size requested.
+    #[cfg(feature = "svg")]
+    pub(crate) fn from_svg(svg: SvgIcon) -> Result<Self, BadIcon> {
+        let (width, height) = svg.size();
+        let image = svg.rasterize(width, height);
+        Ok(Self {
+            images: vec![RawImage::from_rgba(image.rgba, image.width, image.height)],
+            svg: Some(svg),
+        })
+    }
+
+    /// Adds another resolution of this icon from 32bpp RGBA data.
+    pub fn add_rgba(&mut self, rgba: Vec<u8>, width: u32, height: u32) -> Result<(), BadIcon> {
+        let image = RawImage::from_rgba(rgba, width, height);
+        let position = self
+            .images
+            .partition_point(|i| i.width * i.height <= image.width * image.height);
+        self.images.insert(position, image);
+        Ok(())
+    }
+
+    /// Returns the largest resolution of this icon.
+    pub fn to_pixbuf(&self) -> Pixbuf {
+        self.images.last().unwrap().to_pixbuf()
+    }
+
+    /// Returns this icon scaled to `w`x`h`, scaling down the smallest resolution
+    /// that is at least as large, or the largest one if there is none.
+    /// SVG icons are rasterized at exactly that size instead.
+    pub fn to_pixbuf_scale(&self, w: i32, h: i32) -> Pixbuf {
+        #[cfg(feature = "svg")]
+        if let Some(svg) = &self.svg {
+            let image = svg.rasterize(w as u32, h as u32);
+            return RawImage::from_rgba(image.rgba, image.width, image.height).to_pixbuf();
+        }
+
+        let image = self
+            .images
+            .iter()
+            .find(|i| i.width >= w && i.height >= h)
+            .unwrap_or_else(|| self.images.last().unwrap());
+        let pixbuf = image.to_pixbuf();
+        if image.width == w && image.height == h {
+            pixbuf
+        } else {
+            pixbuf
+                .scale_simple(w, h, gdk_pixbuf::InterpType::Bilinear)
+                .unwrap()
+        }
+    }
+
+    /// Like [`PlatformIcon::to_pixbuf_scale`], but replaces the color of every pixel
+    /// with `color`, only keeping their alpha, multiplied by the alpha of `color`.
+    pub fn to_pixbuf_scale_recolored(&self, w: i32, h: i32, color: [u8; 4]) -> Pixbuf {
+        let pixbuf = self.to_pixbuf_scale(w, h);
+        let row_len = pixbuf.width() as usize * 4;
+        let row_stride = pixbuf.rowstride() as usize;
+        // SAFETY: the pixbuf was just created and isn't shared with anything else
+        let pixels = unsafe { pixbuf.pixels() };
+        for row in pixels.chunks_mut(row_stride) {
+            for pixel in row[..row_len].chunks_exact_mut(4) {
+                pixel[0] = color[0];
+                pixel[1] = color[1];
+                pixel[2] = color[2];
+                pixel[3] = (pixel[3] as u16 * color[3] as u16 / 255) as u8;
+            }
+        }
+        pixbuf
+    }
+}
@@ -0,0 +1,1132 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+mod accelerator;
+mod icon;
+
+#[cfg(feature = "x11-global-hotkeys")]
+pub(crate) use accelerator::accelerator_keysym;
+pub(crate) use icon::PlatformIcon;
+
+use crate::{
+    accelerator::{Accelerator, AcceleratorSequence},
+    icon::{Icon, NativeIcon},
+    items::*,
+    util::{AddOp, Counter},
+    AboutMetadata, MenuEvent, MenuItemKind, MenuItemType, Position,
+};
+use accelerator::{parse_accelerator, to_gtk_accelerator_name, to_gtk_mnemonic};
+use gtk4::{gdk, gio, prelude::*};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+static COUNTER: Counter = Counter::new();
+
+/// Name under which the actions of a menu are inserted into windows and popovers.
+const ACTION_GROUP_NAME: &str = "muda";
+
+/// Custom attribute storing the id of the [`MenuChild`] an item was created for,
+/// used to find the item again inside its section.
+const MUDA_ID_ATTRIBUTE: &str = "muda-id";
+
+macro_rules! return_if_predefined_item_not_supported {
+    ($item:tt) => {
+        let child = $item.child();
+        let child_ = child.borrow();
+        match (&child_.item_type, &child_.predefined_item_type) {
+            (
+                MenuItemType::Predefined,
+                PredefinedMenuItemType::Separator
+                | PredefinedMenuItemType::Copy
+                | PredefinedMenuItemType::Cut
+                | PredefinedMenuItemType::Paste
+                | PredefinedMenuItemType::SelectAll
+                | PredefinedMenuItemType::About(_),
+            ) => {}
+            (
+                MenuItemType::Submenu
+                | MenuItemType::MenuItem
+                | MenuItemType::Check
+                | MenuItemType::Icon,
+                _,
+            ) => {}
+            _ => return Ok(()),
+        }
+        drop(child_);
+    };
+}
+
+/// The actions and keyboard shortcuts of every item reachable from a menu,
+/// shared with the windows and popovers displaying it.
+#[derive(Debug, Clone, PartialEq)]
+struct MenuActions {
+    group: gio::SimpleActionGroup,
+    shortcuts: gio::ListStore,
+}
+
+impl MenuActions {
+    fn new() -> Self {
+        Self {
+            group: gio::SimpleActionGroup::new(),
+            shortcuts: gio::ListStore::new::<gtk4::Shortcut>(),
+        }
+    }
+}
+
+pub struct Menu {
+    id: u32,
+    children: Vec<Rc<RefCell<MenuChild>>>,
+    gio_menu: gio::Menu,
+    actions: MenuActions,
+    gtk_menubars: HashMap<u32, (gtk4::PopoverMenuBar, gtk4::ShortcutController)>,
+    gtk_menu: Option<gtk4::PopoverMenu>, // dedicated menu for context menus
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self {
+            id: COUNTER.next(),
+            children: Vec::new(),
+            gio_menu: gio::Menu::new(),
+            actions: MenuActions::new(),
+            gtk_menubars: HashMap::new(),
+            gtk_menu: None,
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn add_menu_item(&mut self, item: &dyn crate::IsMenuItem, op: AddOp) -> crate::Result<()> {
+        return_if_predefined_item_not_supported!(item);
+
+        item.child().borrow_mut().register(&self.actions)?;
+
+        match op {
+            AddOp::Append => self.children.push(item.child()),
+            AddOp::Insert(position) => self.children.insert(position, item.child()),
+        }
+
+        sync_gio_menu(&self.gio_menu, self.items())
+    }
+
+    pub fn remove(&mut self, item: &dyn crate::IsMenuItem) -> crate::Result<()> {
+        let index = self
+            .children
+            .iter()
+            .position(|e| e.borrow().id == item.id())
+            .ok_or(crate::Error::NotAChildOfThisMenu)?;
+        let child = self.children.remove(index);
+
+        child.borrow_mut().unregister(&self.actions);
+
+        sync_gio_menu(&self.gio_menu, self.items())
+    }
+
+    pub fn items(&self) -> Vec<MenuItemKind> {
+        self.children
+            .iter()
+            .map(|c| c.borrow().kind(c.clone()))
+            .collect()
+    }
+
+    pub fn init_for_gtk_window<W, C>(
+        &mut self,
+        window: &W,
+        container: Option<&C>,
+    ) -> crate::Result<()>
+    where
+        W: IsA<gtk4::ApplicationWindow>,
+        C: IsA<gtk4::Box>,
+    {
+        let window = window.upcast_ref::<gtk4::ApplicationWindow>();
+        let id = window.as_ptr() as u32;
+
+        if self.gtk_menubars.contains_key(&id) {
+            return Err(crate::Error::AlreadyInitialized);
+        }
+
+        // gtk is guaranteed to be initialized by now,
+        // so create the shortcuts of items added before that
+        for item in self.items() {
+            item.child_mut().sync_shortcut()?;
+        }
+
+        let menu_bar = gtk4::PopoverMenuBar::from_model(Some(&self.gio_menu));
+
+        let shortcut_controller = gtk4::ShortcutController::for_model(&self.actions.shortcuts);
+        shortcut_controller.set_scope(gtk4::ShortcutScope::Global);
+        shortcut_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+        window.insert_action_group(ACTION_GROUP_NAME, Some(&self.actions.group));
+        window.add_controller(shortcut_controller.clone());
+
+        // add the menubar to the specified box, otherwise on top of the window content
+        if let Some(container) = container {
+            container.prepend(&menu_bar);
+        } else {
+            let vbox = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+            let content = window.child();
+            window.set_child(Some(&vbox));
+            vbox.append(&menu_bar);
+            if let Some(content) = content {
+                vbox.append(&content);
+            }
+        }
+
+        self.gtk_menubars
+            .insert(id, (menu_bar, shortcut_controller));
+
+        Ok(())
+    }
+
+    pub fn remove_for_gtk_window<W>(&mut self, window: &W) -> crate::Result<()>
+    where
+        W: IsA<gtk4::ApplicationWindow>,
+    {
+        let window = window.upcast_ref::<gtk4::ApplicationWindow>();
+        let id = window.as_ptr() as u32;
+
+        // Remove from our cache
+        let (menu_bar, shortcut_controller) = self
+            .gtk_menubars
+            .remove(&id)
+            .ok_or(crate::Error::NotInitialized)?;
+
+        // Remove the [`gtk4::PopoverMenuBar`] from the widget tree
+        if let Some(container) = menu_bar
+            .parent()
+            .and_then(|p| p.downcast::<gtk4::Box>().ok())
+        {
+            container.remove(&menu_bar);
+        }
+
+        // Detach the actions and accelerators from the window
+        window.remove_controller(&shortcut_controller);
+        window.insert_action_group(ACTION_GROUP_NAME, None::<&gio::ActionGroup>);
+
+        Ok(())
+    }
+
+    pub fn hide_for_gtk_window<W>(&mut self, window: &W) -> crate::Result<()>
+    where
+        W: IsA<gtk4::ApplicationWindow>,
+    {
+        self.gtk_menubars
+            .get(&(window.as_ptr() as u32))
+            .ok_or(crate::Error::NotInitialized)?
+            .0
+            .set_visible(false);
+        Ok(())
+    }
+
+    pub fn show_for_gtk_window<W>(&self, window: &W) -> crate::Result<()>
+    where
+        W: IsA<gtk4::ApplicationWindow>,
+    {
+        self.gtk_menubars
+            .get(&(window.as_ptr() as u32))
+            .ok_or(crate::Error::NotInitialized)?
+            .0
+            .set_visible(true);
+        Ok(())
+    }
+
+    pub fn is_visible_on_gtk_window<W>(&self, window: &W) -> bool
+    where
+        W: IsA<gtk4::ApplicationWindow>,
+    {
+        self.gtk_menubars
+            .get(&(window.as_ptr() as u32))
+            .map(|(m, _)| m.is_visible())
+            .unwrap_or(false)
+    }
+
+    pub fn gtk_menubar_for_gtk_window<W>(&self, window: &W) -> Option<gtk4::PopoverMenuBar>
+    where
+        W: IsA<gtk4::ApplicationWindow>,
+    {
+        self.gtk_menubars
+            .get(&(window.as_ptr() as u32))
+            .map(|(m, _)| m.clone())
+    }
+
+    pub fn show_context_menu_for_gtk_window(
+        &mut self,
+        widget: &impl IsA<gtk4::Widget>,
+        position: Option<Position>,
+    ) {
+        show_context_menu(&self.gtk_context_menu(), widget, position)
+    }
+
+    pub fn gtk_context_menu(&mut self) -> gtk4::PopoverMenu {
+        if self.gtk_menu.is_none() {
+            self.gtk_menu = Some(create_popover_menu(&self.gio_menu, &self.actions));
+        }
+
+        self.gtk_menu.as_ref().unwrap().clone()
+    }
+}
+
+/// A generic child in a menu
+#[derive(Debug, Default)]
+pub struct MenuChild {
+    // shared fields between submenus and menu items
+    item_type: MenuItemType,
+    text: String,
+    enabled: bool,
+    id: u32,
+
+    action: Option<gio::SimpleAction>,
+    menu_actions: Vec<MenuActions>,
+    gio_sections: Vec<gio::Menu>,
+
+    // menu item fields
+    accelerator: Option<Accelerator>,
+    /// triggers of the shortcut in addition to `accelerator`, but not displayed
+    alternate_accelerators: Vec<Accelerator>,
+    shortcut: Option<gtk4::Shortcut>,
+
+    // predefined menu item fields
+    predefined_item_type: PredefinedMenuItemType,
+
+    // check menu item fields
+    checked: bool,
+    /// gio actions have no mixed state, so it is only kept track of.
+    mixed: Rc<Cell<bool>>,
+
+    // icon menu item fields
+    icon: Option<Icon>,
+    icon_name: Option<String>,
+
+    // submenu fields
+    pub children: Option<Vec<Rc<RefCell<MenuChild>>>>,
+    gio_menu: gio::Menu,
+    gtk_menu: Option<(MenuActions, gtk4::PopoverMenu)>, // dedicated menu for context menus
+
+    // global hotkey fields
+    #[cfg(feature = "x11-global-hotkeys")]
+    pub global_hotkey: super::x11_hotkeys::HotkeyGuard,
+}
+
+/// Constructors
+impl MenuChild {
+    pub fn new(text: &str, enabled: bool, accelerator: Option<Accelerator>) -> Self {
+        Self {
+            text: text.to_string(),
+            enabled,
+            accelerator,
+            id: COUNTER.next(),
+            item_type: MenuItemType::MenuItem,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_submenu(text: &str, enabled: bool) -> Self {
+        Self {
+            text: text.to_string(),
+            enabled,
+            id: COUNTER.next(),
+            children: Some(Vec::new()),
+            item_type: MenuItemType::Submenu,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn new_predefined(item_type: PredefinedMenuItemType, text: Option<String>) -> Self {
+        Self {
+            text: text.unwrap_or_else(|| item_type.text().to_string()),
+            enabled: true,
+            accelerator: item_type.accelerator(),
+            id: COUNTER.next(),
+            item_type: MenuItemType::Predefined,
+            predefined_item_type: item_type,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_check(
+        text: &str,
+        enabled: bool,
+        checked: bool,
+        accelerator: Option<Accelerator>,
+    ) -> Self {
+        Self {
+            text: text.to_string(),
+            enabled,
+            checked,
+            accelerator,
+            id: COUNTER.next(),
+            item_type: MenuItemType::Check,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_icon(
+        text: &str,
+        enabled: bool,
+        icon: Option<Icon>,
+        accelerator: Option<Accelerator>,
+    ) -> Self {
+        Self {
+            text: text.to_string(),
+            enabled,
+            icon,
+            accelerator,
+            id: COUNTER.next(),
+            item_type: MenuItemType::Icon,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_native_icon(
+        text: &str,
+        enabled: bool,
+        native_icon: Option<NativeIcon>,
+        accelerator: Option<Accelerator>,
+    ) -> Self {
+        Self {
+            text: text.to_string(),
+            enabled,
+            icon_name: native_icon.map(|i| i.to_freedesktop_name().to_string()),
+            accelerator,
+            id: COUNTER.next(),
+            item_type: MenuItemType::Icon,
+            ..Default::default()
+        }
+    }
+}
+
+/// Shared methods
+impl MenuChild {
+    pub(crate) fn item_type(&self) -> MenuItemType {
+        self.item_type
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        let text = to_gtk_mnemonic(text);
+        self.update_gio_items(|i| i.set_label(Some(&text)));
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if let Some(action) = &self.action {
+            action.set_enabled(enabled);
+        }
+    }
+
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.accelerator
+            .into_iter()
+            .chain(self.alternate_accelerators.iter().copied())
+            .collect()
+    }
+
+    pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
+        self.set_accelerators(accelerator.into_iter().collect())
+    }
+
+    pub fn set_accelerators(&mut self, accelerators: Vec<Accelerator>) -> crate::Result<()> {
+        for accelerator in &accelerators {
+            parse_accelerator(accelerator)?;
+        }
+
+        let mut accelerators = accelerators.into_iter();
+        let accelerator = accelerators.next();
+        let accel = accelerator
+            .as_ref()
+            .map(to_gtk_accelerator_name)
+            .transpose()?;
+
+        self.accelerator = accelerator;
+        self.alternate_accelerators = accelerators.collect();
+        self.update_gio_items(|i| {
+            i.set_attribute_value("accel", accel.as_ref().map(|a| a.to_variant()).as_ref())
+        });
+
+        self.sync_shortcut()
+    }
+
+    pub fn set_accelerator_sequence(
+        &mut self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        // gio menu models can only display single key accelerators
+        let accelerator = sequence
+            .filter(|s| s.accelerators().len() == 1)
+            .map(|s| s.accelerators()[0]);
+        self.set_accelerator(accelerator)
+    }
+}
+
+/// CheckMenuItem methods
+impl MenuChild {
+    pub fn is_checked(&self) -> bool {
+        self.action
+            .as_ref()
+            .and_then(|a| a.state())
+            .and_then(|s| s.get::<bool>())
+            .unwrap_or(self.checked)
+    }
+
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        self.mixed.set(false);
+        if let Some(action) = &self.action {
+            action.set_state(&checked.to_variant());
+        }
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.mixed.get()
+    }
+
+    pub fn set_mixed(&mut self, mixed: bool) {
+        self.mixed.set(mixed);
+    }
+}
+
+/// IconMenuItem methods
+impl MenuChild {
+    pub fn set_icon(&mut self, icon: Option<Icon>) {
+        self.icon = icon;
+        self.icon_name = None;
+        self.update_gio_icons();
+    }
+
+    pub fn set_icon_name(&mut self, icon_name: Option<&str>) {
+        self.icon_name = icon_name.map(|n| n.to_string());
+        self.icon = None;
+        self.update_gio_icons();
+    }
+
+    fn update_gio_icons(&mut self) {
+        let icon = self.gio_icon();
+        self.update_gio_items(|i| match &icon {
+            Some(icon) => i.set_icon(icon),
+            None => i.set_attribute_value(gio::MENU_ATTRIBUTE_ICON, None),
+        });
+    }
+
+    fn gio_icon(&self) -> Option<gio::Icon> {
+        match (&self.icon, &self.icon_name) {
+            (Some(icon), _) => {
+                // gtk scales gio icons down to the scale factor of the monitor
+                // the menu is shown on, so provide the resolution for the highest one
+                let size = 16 * max_scale_factor();
+                let is_dark = prefers_dark_theme();
+                let variant = match &icon.dark {
+                    Some(dark) if is_dark => dark,
+                    _ => &icon.inner,
+                };
+                let pixbuf = if icon.is_template {
+                    let c = if is_dark { 0xff } else { 0 };
+                    variant.to_pixbuf_scale_recolored(size, size, [c, c, c, 0xff])
+                } else {
+                    variant.to_pixbuf_scale(size, size)
+                };
+                Some(pixbuf.upcast())
+            }
+            (None, Some(icon_name)) => Some(gio::ThemedIcon::new(icon_name).upcast()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Submenu methods
+impl MenuChild {
+    pub fn add_menu_item(&mut self, item: &dyn crate::IsMenuItem, op: AddOp) -> crate::Result<()> {
+        return_if_predefined_item_not_supported!(item);
+
+        for actions in self.all_menu_actions() {
+            item.child().borrow_mut().register(&actions)?;
+        }
+
+        match op {
+            AddOp::Append => self.children.as_mut().unwrap().push(item.child()),
+            AddOp::Insert(position) => self
+                .children
+                .as_mut()
+                .unwrap()
+                .insert(position, item.child()),
+        }
+
+        sync_gio_menu(&self.gio_menu, self.items())
+    }
+
+    pub fn remove(&mut self, item: &dyn crate::IsMenuItem) -> crate::Result<()> {
+        let index = self
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .position(|e| e.borrow().id == item.id())
+            .ok_or(crate::Error::NotAChildOfThisMenu)?;
+        let child = self.children.as_mut().unwrap().remove(index);
+
+        for actions in self.all_menu_actions() {
+            child.borrow_mut().unregister(&actions);
+        }
+
+        sync_gio_menu(&self.gio_menu, self.items())
+    }
+
+    pub fn items(&self) -> Vec<MenuItemKind> {
+        self.children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|c| c.borrow().kind(c.clone()))
+            .collect()
+    }
+
+    pub fn show_context_menu_for_gtk_window(
+        &mut self,
+        widget: &impl IsA<gtk4::Widget>,
+        position: Option<Position>,
+    ) {
+        show_context_menu(&self.gtk_context_menu(), widget, position)
+    }
+
+    pub fn gtk_context_menu(&mut self) -> gtk4::PopoverMenu {
+        if self.gtk_menu.is_none() {
+            let actions = MenuActions::new();
+            for item in self.items() {
+                let _ = item.child_mut().register(&actions);
+            }
+            let menu = create_popover_menu(&self.gio_menu, &actions);
+            self.gtk_menu = Some((actions, menu));
+        }
+
+        self.gtk_menu.as_ref().unwrap().1.clone()
+    }
+
+    /// The action groups of the menus this submenu is part of,
+    /// including the one of its own context menu.
+    fn all_menu_actions(&self) -> Vec<MenuActions> {
+        self.menu_actions
+            .iter()
+            .chain(self.gtk_menu.as_ref().map(|(actions, _)| actions))
+            .cloned()
+            .collect()
+    }
+}
+
+/// gio menu model methods
+impl MenuChild {
+    fn action_name(&self) -> String {
+        format!("muda-{}", self.id)
+    }
+
+    fn detailed_action_name(&self) -> String {
+        format!("{}.{}", ACTION_GROUP_NAME, self.action_name())
+    }
+
+    /// Whether this item is only represented as a section boundary.
+    fn is_separator(&self) -> bool {
+        matches!(self.predefined_item_type, PredefinedMenuItemType::Separator)
+    }
+
+    /// Predefined edit items only display their accelerator, the focused widget
+    /// handles the key presses itself.
+    fn has_display_only_accelerator(&self) -> bool {
+        matches!(
+            self.predefined_item_type,
+            PredefinedMenuItemType::Copy
+                | PredefinedMenuItemType::Cut
+                | PredefinedMenuItemType::Paste
+                | PredefinedMenuItemType::SelectAll
+        )
+    }
+
+    fn action(&mut self) -> gio::SimpleAction {
+        if let Some(action) = &self.action {
+            return action.clone();
+        }
+
+        let name = self.action_name();
+        let action = match self.item_type {
+            MenuItemType::Check => {
+                gio::SimpleAction::new_stateful(&name, None, &self.checked.to_variant())
+            }
+            _ => gio::SimpleAction::new(&name, None),
+        };
+        action.set_enabled(self.enabled);
+
+        let id = self.id;
+        match self.item_type {
+            MenuItemType::Submenu => {}
+            MenuItemType::Check => {
+                let mixed = self.mixed.clone();
+                action.connect_activate(move |action, _| {
+                    mixed.set(false);
+                    let checked = action
+                        .state()
+                        .and_then(|s| s.get::<bool>())
+                        .unwrap_or_default();
+                    action.set_state(&(!checked).to_variant());
+
+                    MenuEvent::send(crate::MenuEvent { id });
+                });
+            }
+            MenuItemType::Predefined => {
+                let predefined_item_type = self.predefined_item_type.clone();
+                action.connect_activate(move |_, _| match &predefined_item_type {
+                    PredefinedMenuItemType::About(Some(metadata)) => show_about_dialog(metadata),
+                    PredefinedMenuItemType::About(None) => {}
+                    _ => {
+                        if let Some(widget) = active_window().and_then(|w| RootExt::focus(&w)) {
+                            let _ = widget
+                                .activate_action(predefined_item_type.gtk_widget_action(), None);
+                        }
+                    }
+                });
+            }
+            MenuItemType::MenuItem | MenuItemType::Icon => {
+                action.connect_activate(move |_, _| {
+                    MenuEvent::send(crate::MenuEvent { id });
+                });
+            }
+        }
+
+        self.action = Some(action.clone());
+        action
+    }
+
+    /// Adds the actions and shortcuts of this item, and the items of its submenu,
+    /// to the action group of a menu.
+    fn register(&mut self, actions: &MenuActions) -> crate::Result<()> {
+        if self.is_separator() {
+            return Ok(());
+        }
+
+        actions.group.add_action(&self.action());
+        self.menu_actions.push(actions.clone());
+        self.sync_shortcut()?;
+
+        if self.item_type == MenuItemType::Submenu {
+            for item in self.items() {
+                item.child_mut().register(actions)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unregister(&mut self, actions: &MenuActions) {
+        let Some(index) = self.menu_actions.iter().position(|a| a == actions) else {
+            return;
+        };
+        self.menu_actions.remove(index);
+
+        // the same item might still be reachable through another submenu
+        if !self.menu_actions.contains(actions) {
+            actions.group.remove_action(&self.action_name());
+            if let Some(position) = self
+                .shortcut
+                .as_ref()
+                .and_then(|s| actions.shortcuts.find(s))
+            {
+                actions.shortcuts.remove(position);
+            }
+        }
+
+        if self.item_type == MenuItemType::Submenu {
+            for item in self.items() {
+                item.child_mut().unregister(actions);
+            }
+        }
+    }
+
+    /// Creates or updates the keyboard shortcut of this item and makes sure it is part
+    /// of every menu it is registered with.
+    ///
+    /// Shortcuts can only be created once gtk is initialized, so this is a no-op before that
+    /// and is called again when the menu is added to a window.
+    fn sync_shortcut(&mut self) -> crate::Result<()> {
+        if self.item_type == MenuItemType::Submenu {
+            for item in self.items() {
+                item.child_mut().sync_shortcut()?;
+            }
+            return Ok(());
+        }
+
+        if self.is_separator()
+            || self.has_display_only_accelerator()
+            || self.menu_actions.is_empty()
+            || !gtk4::is_initialized_main_thread()
+        {
+            return Ok(());
+        }
+
+        let mut triggers = Vec::new();
+        for accelerator in self.accelerators() {
+            let (mods, key) = parse_accelerator(&accelerator)?;
+            triggers.push(gtk4::KeyvalTrigger::new(key, mods).upcast::<gtk4::ShortcutTrigger>());
+        }
+        let trigger = triggers
+            .into_iter()
+            .rev()
+            .reduce(|alternative, trigger| {
+                gtk4::AlternativeTrigger::new(trigger, alternative).upcast()
+            })
+            .unwrap_or_else(|| gtk4::NeverTrigger::get().upcast());
+
+        let shortcut = match &self.shortcut {
+            Some(shortcut) => {
+                shortcut.set_trigger(Some(trigger));
+                shortcut.clone()
+            }
+            None => {
+                let action = gtk4::NamedAction::new(&self.detailed_action_name());
+                let shortcut = gtk4::Shortcut::new(Some(trigger), Some(action));
+                self.shortcut = Some(shortcut.clone());
+                shortcut
+            }
+        };
+
+        for actions in &self.menu_actions {
+            if actions.shortcuts.find(&shortcut).is_none() {
+                actions.shortcuts.append(&shortcut);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create_gio_item(&mut self) -> crate::Result<gio::MenuItem> {
+        let label = to_gtk_mnemonic(&self.text);
+        let item = if self.item_type == MenuItemType::Submenu {
+            let item = gio::MenuItem::new_submenu(Some(&label), &self.gio_menu);
+            // the enabled state of a submenu is controlled by its `submenu-action`
+            item.set_attribute_value(
+                "submenu-action",
+                Some(&self.detailed_action_name().to_variant()),
+            );
+            item
+        } else {
+            gio::MenuItem::new(Some(&label), Some(&self.detailed_action_name()))
+        };
+
+        if let Some(accelerator) = &self.accelerator {
+            let accel = to_gtk_accelerator_name(accelerator)?;
+            item.set_attribute_value("accel", Some(&accel.to_variant()));
+        }
+
+        if let Some(icon) = self.gio_icon() {
+            item.set_icon(&icon);
+        }
+
+        item.set_attribute_value(MUDA_ID_ATTRIBUTE, Some(&self.id.to_variant()));
+
+        Ok(item)
+    }
+
+    /// Applies `f` to this item in every section it is still part of,
+    /// and forgets about the sections it has been removed from.
+    fn update_gio_items<F: Fn(&gio::MenuItem)>(&mut self, f: F) {
+        let id = self.id;
+        self.gio_sections
+            .retain(|section| match gio_item_position(section, id) {
+                Some(position) => {
+                    let item = gio::MenuItem::from_model(section, position);
+                    f(&item);
+                    section.remove(position);
+                    section.insert_item(position, &item);
+                    true
+                }
+                None => false,
+            });
+    }
+}
+
+/// Updates `gio_menu` in place to show `items`, starting a new section for every separator.
+///
+/// Only the sections and items that changed are touched, so that popovers opened
+/// from `gio_menu` aren't closed by unrelated changes.
+fn sync_gio_menu(gio_menu: &gio::Menu, items: Vec<MenuItemKind>) -> crate::Result<()> {
+    let mut layout: Vec<Vec<MenuItemKind>> = vec![Vec::new()];
+    for item in items {
+        if item.child().is_separator() {
+            layout.push(Vec::new());
+        } else {
+            layout.last_mut().unwrap().push(item);
+        }
+    }
+    layout.retain(|section| !section.is_empty());
+
+    let sections = (0..gio_menu.n_items())
+        .filter_map(|i| {
+            gio_menu
+                .item_link(i, gio::MENU_LINK_SECTION)
+                .and_then(|s| s.downcast::<gio::Menu>().ok())
+        })
+        .collect::<Vec<_>>();
+
+    for (i, items) in layout.iter().enumerate() {
+        let section = match sections.get(i) {
+            Some(section) => section.clone(),
+            None => {
+                let section = gio::Menu::new();
+                gio_menu.append_section(None, &section);
+                section
+            }
+        };
+        sync_gio_section(&section, items)?;
+    }
+
+    for (i, section) in sections.iter().enumerate().skip(layout.len()).rev() {
+        section.remove_all();
+        gio_menu.remove(i as i32);
+    }
+
+    Ok(())
+}
+
+/// Updates `section` in place to show `items`, keeping the items already in it.
+fn sync_gio_section(section: &gio::Menu, items: &[MenuItemKind]) -> crate::Result<()> {
+    for i in (0..section.n_items()).rev() {
+        let id = section
+            .item_attribute_value(i, MUDA_ID_ATTRIBUTE, None)
+            .and_then(|v| v.get::<u32>());
+        if !items.iter().any(|item| Some(item.id()) == id) {
+            section.remove(i);
+        }
+    }
+
+    for (position, item) in items.iter().enumerate() {
+        let position = position as i32;
+        let gio_item = match gio_item_position(section, item.id()) {
+            Some(p) if p == position => continue,
+            Some(p) => {
+                let gio_item = gio::MenuItem::from_model(section, p);
+                section.remove(p);
+                gio_item
+            }
+            None => {
+                let mut child = item.child_mut();
+                if !child.gio_sections.contains(section) {
+                    child.gio_sections.push(section.clone());
+                }
+                child.create_gio_item()?
+            }
+        };
+        section.insert_item(position, &gio_item);
+    }
+
+    Ok(())
+}
+
+fn gio_item_position(section: &gio::Menu, id: u32) -> Option<i32> {
+    (0..section.n_items()).find(|i| {
+        section
+            .item_attribute_value(*i, MUDA_ID_ATTRIBUTE, None)
+            .and_then(|v| v.get::<u32>())
+            == Some(id)
+    })
+}
+
+fn create_popover_menu(gio_menu: &gio::Menu, actions: &MenuActions) -> gtk4::PopoverMenu {
+    let popover = gtk4::PopoverMenu::from_model(Some(gio_menu));
+    popover.set_has_arrow(false);
+    popover.set_halign(gtk4::Align::Start);
+    popover.insert_action_group(ACTION_GROUP_NAME, Some(&actions.group));
+    popover
+}
+
+fn show_context_menu(
+    popover: &gtk4::PopoverMenu,
+    widget: &impl IsA<gtk4::Widget>,
+    position: Option<Position>,
+) {
+    let pos = if let Some(pos) = position {
+        pos.to_logical::<i32>(widget.scale_factor() as _).into()
+    } else {
+        widget
+            .native()
+            .and_then(|native| {
+                let surface = native.surface()?;
+                let pointer = surface.display().default_seat()?.pointer()?;
+                let (x, y, _) = surface.device_position(&pointer)?;
+                let (dx, dy) = native.surface_transform();
+                Some(((x - dx) as i32, (y - dy) as i32))
+            })
+            .unwrap_or_default()
+    };
+
+    if popover.parent().as_ref() != Some(widget.upcast_ref()) {
+        if popover.parent().is_some() {
+            popover.unparent();
+        }
+        popover.set_parent(widget);
+    }
+
+    popover.set_pointing_to(Some(&gdk::Rectangle::new(pos.0, pos.1, 1, 1)));
+    popover.popup();
+}
+
+fn active_window() -> Option<gtk4::Window> {
+    gtk4::Window::list_toplevels()
+        .into_iter()
+        .filter_map(|w| w.downcast::<gtk4::Window>().ok())
+        .find(|w| w.is_active())
+}
+
+fn prefers_dark_theme() -> bool {
+    gtk4::is_initialized_main_thread()
+        && gtk4::Settings::default().is_some_and(|s| s.is_gtk_application_prefer_dark_theme())
+}
+
+/// Returns the highest scale factor among the connected monitors,
+/// or `2` if gtk isn't initialized yet.
+fn max_scale_factor() -> i32 {
+    if !gtk4::is_initialized_main_thread() {
+        return 2;
+    }
+
+    gdk::Display::default()
+        .map(|display| {
+            display
+                .monitors()
+                .iter::<gdk::Monitor>()
+                .flatten()
+                .map(|monitor| monitor.scale_factor())
+                .max()
+                .unwrap_or(1)
+        })
+        .unwrap_or(1)
+}
+
+fn show_about_dialog(metadata: &AboutMetadata) {
+    let mut builder = gtk4::AboutDialog::builder().modal(true).resizable(false);
+
+    if let Some(name) = &metadata.name {
+        builder = builder.program_name(name);
+    }
+    if let Some(version) = &metadata.full_version() {
+        builder = builder.version(version);
+    }
+    if let Some(authors) = &metadata.authors {
+        builder = builder.authors(authors.clone());
+    }
+    if let Some(comments) = &metadata.comments {
+        builder = builder.comments(comments);
+    }
+    if let Some(copyright) = &metadata.copyright {
+        builder = builder.copyright(copyright);
+    }
+    if let Some(license) = &metadata.license {
+        builder = builder.license(license);
+    }
+    if let Some(website) = &metadata.website {
+        builder = builder.website(website);
+    }
+    if let Some(website_label) = &metadata.website_label {
+        builder = builder.website_label(website_label);
+    }
+    if let Some(icon) = &metadata.icon {
+        builder = builder.logo(&gdk::Texture::for_pixbuf(&icon.inner.to_pixbuf()));
+    }
+    if let Some(window) = active_window() {
+        builder = builder.transient_for(&window);
+    }
+
+    builder.build().present();
+}
+
+impl PredefinedMenuItemType {
+    /// The action of the focused widget that implements this item.
+    fn gtk_widget_action(&self) -> &str {
+        match self {
+            PredefinedMenuItemType::Copy => "clipboard.copy",
+            PredefinedMenuItemType::Cut => "clipboard.cut",
+            PredefinedMenuItemType::Paste => "clipboard.paste",
+            PredefinedMenuItemType::SelectAll => "selection.select-all",
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn test_gio_menu_sync() {
+    use crate::{CheckMenuItem, IsMenuItem, MenuItem, PredefinedMenuItem};
+
+    let labels = |gio_menu: &gio::Menu| {
+        (0..gio_menu.n_items())
+            .filter_map(|i| gio_menu.item_link(i, gio::MENU_LINK_SECTION))
+            .map(|section| {
+                (0..section.n_items())
+                    .filter_map(|i| {
+                        section
+                            .item_attribute_value(i, gio::MENU_ATTRIBUTE_LABEL, None)
+                            .and_then(|v| v.get::<String>())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut menu = Menu::new();
+    let open = MenuItem::new("&Open", true, None);
+    let separator = PredefinedMenuItem::separator();
+    let wrap = CheckMenuItem::new("Wrap", true, false, None);
+    for item in [&open as &dyn IsMenuItem, &separator, &wrap] {
+        menu.add_menu_item(item, AddOp::Append).unwrap();
+    }
+    assert_eq!(labels(&menu.gio_menu), [["_Open"], ["Wrap"]]);
+    let sections = (0..menu.gio_menu.n_items())
+        .map(|i| menu.gio_menu.item_link(i, gio::MENU_LINK_SECTION).unwrap())
+        .collect::<Vec<_>>();
+
+    // changes only touch the affected section
+    let save = MenuItem::new("Save", true, None);
+    menu.add_menu_item(&save, AddOp::Insert(1)).unwrap();
+    wrap.set_text("Word Wrap");
+    assert_eq!(
+        labels(&menu.gio_menu),
+        [vec!["_Open", "Save"], vec!["Word Wrap"]]
+    );
+    assert_eq!(
+        menu.gio_menu.item_link(1, gio::MENU_LINK_SECTION).unwrap(),
+        sections[1]
+    );
+
+    open.set_enabled(false);
+    let group = menu.actions.group.clone();
+    let action = |item: &dyn IsMenuItem| {
+        let name = item.child().borrow().action_name();
+        group.lookup_action(&name)
+    };
+    assert!(!action(&open).unwrap().is_enabled());
+
+    menu.remove(&separator).unwrap();
+    menu.remove(&open).unwrap();
+    assert_eq!(labels(&menu.gio_menu), [["Save", "Word Wrap"]]);
+    assert_eq!(
+        menu.gio_menu.item_link(0, gio::MENU_LINK_SECTION).unwrap(),
+        sections[0]
+    );
+    assert!(action(&open).is_none());
+}
@@ -12,6 +12,15 @@ impl Accelerator {
     ///
     /// Returns the empty string if no key equivalent is known.
     pub fn key_equivalent(self) -> crate::Result<String> {
+        let unsupported = self.mods & (Modifiers::HYPER | Modifiers::META);
+        if !unsupported.is_empty() {
+            return Err(crate::Error::UnsupportedAcceleratorModifiers(unsupported));
+        }
+
+        if let Some(c) = self.logical {
+            return Ok(c.to_string());
+        }
+
         Ok(match self.key {
             Code::KeyA => "a".into(),
             Code::KeyB => "b".into(),
@@ -103,6 +112,29 @@ impl Accelerator {
             Code::F22 => "\u{F719}".into(),
             Code::F23 => "\u{F71A}".into(),
             Code::F24 => "\u{F71B}".into(),
+            Code::Pause => "\u{F730}".into(),
+            Code::ContextMenu => "\u{F735}".into(),
+            Code::NumLock => "\u{F739}".into(),
+            Code::Help => "\u{F746}".into(),
+            // the numeric pad keys are told apart by `NSNumericPadKeyMask`
+            Code::Numpad0 => "0".into(),
+            Code::Numpad1 => "1".into(),
+            Code::Numpad2 => "2".into(),
+            Code::Numpad3 => "3".into(),
+            Code::Numpad4 => "4".into(),
+            Code::Numpad5 => "5".into(),
+            Code::Numpad6 => "6".into(),
+            Code::Numpad7 => "7".into(),
+            Code::Numpad8 => "8".into(),
+            Code::Numpad9 => "9".into(),
+            Code::NumpadAdd => "+".into(),
+            Code::NumpadComma => ",".into(),
+            Code::NumpadDecimal => ".".into(),
+            Code::NumpadDivide => "/".into(),
+            Code::NumpadEnter => "\u{0003}".into(),
+            Code::NumpadEqual => "=".into(),
+            Code::NumpadMultiply => "*".into(),
+            Code::NumpadSubtract => "-".into(),
             key => return Err(crate::Error::UnrecognizedAcceleratorCode(key.to_string())),
         })
     }
@@ -117,12 +149,86 @@ impl Accelerator {
         if mods.contains(Modifiers::SUPER) {
             flags.insert(NSEventModifierFlags::NSCommandKeyMask);
         }
-        if mods.contains(Modifiers::ALT) {
+        if mods.intersects(Modifiers::ALT | Modifiers::ALT_GRAPH) {
             flags.insert(NSEventModifierFlags::NSAlternateKeyMask);
         }
         if mods.contains(Modifiers::CONTROL) {
             flags.insert(NSEventModifierFlags::NSControlKeyMask);
         }
+        if is_numpad_key(self.key) {
+            flags.insert(NSEventModifierFlags::NSNumericPadKeyMask);
+        }
         flags
     }
 }
+
+fn is_numpad_key(key: Code) -> bool {
+    matches!(
+        key,
+        Code::Numpad0
+            | Code::Numpad1
+            | Code::Numpad2
+            | Code::Numpad3
+            | Code::Numpad4
+            | Code::Numpad5
+            | Code::Numpad6
+            | Code::Numpad7
+            | Code::Numpad8
+            | Code::Numpad9
+            | Code::NumpadAdd
+            | Code::NumpadComma
+            | Code::NumpadDecimal
+            | Code::NumpadDivide
+            | Code::NumpadEnter
+            | Code::NumpadEqual
+            | Code::NumpadMultiply
+            | Code::NumpadSubtract
+    )
+}
+
+#[test]
+fn test_key_equivalent_codes() {
+    // AppKit has no key equivalents for these keys, they are handled by the system
+    // or by input methods before reaching the menu.
+    let unsupported = [
+        Code::CapsLock,
+        Code::AudioVolumeDown,
+        Code::AudioVolumeUp,
+        Code::AudioVolumeMute,
+        Code::MediaPlayPause,
+        Code::MediaStop,
+        Code::MediaTrackNext,
+        Code::MediaTrackPrevious,
+        Code::MediaSelect,
+        Code::BrowserBack,
+        Code::BrowserForward,
+        Code::BrowserRefresh,
+        Code::BrowserStop,
+        Code::BrowserSearch,
+        Code::BrowserFavorites,
+        Code::BrowserHome,
+        Code::LaunchMail,
+        Code::LaunchApp1,
+        Code::LaunchApp2,
+        Code::IntlBackslash,
+        Code::IntlRo,
+        Code::IntlYen,
+        Code::Convert,
+        Code::NonConvert,
+        Code::KanaMode,
+        Code::Lang1,
+        Code::Lang2,
+        Code::Lang3,
+        Code::Lang4,
+        Code::Lang5,
+    ];
+
+    for code in crate::accelerator::PARSEABLE_CODES {
+        let accelerator = Accelerator::new(None, *code);
+        assert_eq!(
+            accelerator.key_equivalent().is_ok(),
+            !unsupported.contains(code),
+            "{code}"
+        );
+    }
+}
@@ -2,35 +2,42 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+#[cfg(feature = "svg")]
+use crate::icon::SvgIcon;
 use crate::icon::{BadIcon, RgbaIcon};
 use std::io::Cursor;
 
 #[derive(Debug, Clone)]
-pub struct PlatformIcon(RgbaIcon);
+pub struct PlatformIcon {
+    rgba: RgbaIcon,
+    #[cfg(feature = "svg")]
+    svg: Option<SvgIcon>,
+}
 
 impl PlatformIcon {
     pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
-        Ok(PlatformIcon(RgbaIcon::from_rgba(rgba, width, height)?))
+        Ok(PlatformIcon {
+            rgba: RgbaIcon::from_rgba(rgba, width, height)?,
+            #[cfg(feature = "svg")]
+            svg: None,
+        })
+    }
+
+    #[cfg(feature = "svg")]
+    pub(crate) fn from_svg(svg: SvgIcon) -> Result<Self, BadIcon> {
+        let (width, height) = svg.size();
+        Ok(PlatformIcon {
+            rgba: svg.rasterize(width, height),
+            svg: Some(svg),
+        })
     }
 
     pub fn get_size(&self) -> (u32, u32) {
-        (self.0.width, self.0.height)
+        (self.rgba.width, self.rgba.height)
     }
 
     pub fn to_png(&self) -> Vec<u8> {
-        let mut png = Vec::new();
-
-        {
-            let mut encoder =
-                png::Encoder::new(Cursor::new(&mut png), self.0.width as _, self.0.height as _);
-            encoder.set_color(png::ColorType::Rgba);
-            encoder.set_depth(png::BitDepth::Eight);
-
-            let mut writer = encoder.write_header().unwrap();
-            writer.write_image_data(&self.0.rgba).unwrap();
-        }
-
-        png
+        rgba_to_png(&self.rgba)
     }
 
     pub unsafe fn to_nsimage(&self, fixed_height: Option<f64>) -> cocoa::base::id {
@@ -41,7 +48,6 @@ impl PlatformIcon {
         };
 
         let (width, height) = self.get_size();
-        let icon = self.to_png();
 
         let (icon_width, icon_height) = match fixed_height {
             Some(fixed_height) => {
@@ -54,6 +60,11 @@ impl PlatformIcon {
             None => (width as f64, height as f64),
         };
 
+        let icon = match self.rasterize_svg(icon_width, icon_height) {
+            Some(rgba) => rgba_to_png(&rgba),
+            None => self.to_png(),
+        };
+
         let nsdata = NSData::dataWithBytes_length_(
             nil,
             icon.as_ptr() as *const std::os::raw::c_void,
@@ -66,4 +77,43 @@ impl PlatformIcon {
 
         nsimage
     }
+
+    /// Rasterizes SVG icons at `width`x`height` points for the main screen's backing scale factor.
+    #[cfg(feature = "svg")]
+    unsafe fn rasterize_svg(&self, width: f64, height: f64) -> Option<RgbaIcon> {
+        use cocoa::{appkit::NSScreen, base::nil};
+
+        let svg = self.svg.as_ref()?;
+        let screen = NSScreen::mainScreen(nil);
+        let scale = if screen == nil {
+            1.0
+        } else {
+            NSScreen::backingScaleFactor(screen)
+        };
+        Some(svg.rasterize(
+            (width * scale).round() as u32,
+            (height * scale).round() as u32,
+        ))
+    }
+
+    #[cfg(not(feature = "svg"))]
+    unsafe fn rasterize_svg(&self, _width: f64, _height: f64) -> Option<RgbaIcon> {
+        None
+    }
+}
+
+fn rgba_to_png(icon: &RgbaIcon) -> Vec<u8> {
+    let mut png = Vec::new();
+
+    {
+        let mut encoder =
+            png::Encoder::new(Cursor::new(&mut png), icon.width as _, icon.height as _);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&icon.rgba).unwrap();
+    }
+
+    png
 }
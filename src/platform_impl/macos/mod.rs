@@ -22,12 +22,12 @@ use objc::{
     runtime::{Class, Object, Sel},
 };
 
-use self::util::{app_name_string, strip_mnemonic};
+use self::util::app_name_string;
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, AcceleratorSequence},
     icon::{Icon, NativeIcon},
     items::*,
-    util::{AddOp, Counter},
+    util::{strip_mnemonic, AddOp, Counter},
     IsMenuItem, LogicalPosition, MenuEvent, MenuItemKind, MenuItemType, Position,
 };
 
@@ -159,12 +159,15 @@ pub struct MenuChild {
 
     // menu item fields
     accelerator: Option<Accelerator>,
+    /// only stored, as a menu item has a single key equivalent
+    alternate_accelerators: Vec<Accelerator>,
 
     // predefined menu item fields
     predefined_item_type: PredefinedMenuItemType,
 
     // check menu item fields
     checked: bool,
+    mixed: bool,
 
     // icon menu item fields
     icon: Option<Icon>,
@@ -185,8 +188,10 @@ impl Default for MenuChild {
             enabled: Default::default(),
             ns_menu_items: Default::default(),
             accelerator: Default::default(),
+            alternate_accelerators: Default::default(),
             predefined_item_type: Default::default(),
             checked: Default::default(),
+            mixed: Default::default(),
             icon: Default::default(),
             native_icon: Default::default(),
             children: Default::default(),
@@ -352,6 +357,30 @@ impl MenuChild {
         }
     }
 
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.accelerator
+            .into_iter()
+            .chain(self.alternate_accelerators.iter().copied())
+            .collect()
+    }
+
+    pub fn set_accelerators(&mut self, accelerators: Vec<Accelerator>) -> crate::Result<()> {
+        let mut accelerators = accelerators.into_iter();
+        let accelerator = accelerators.next();
+        let alternate_accelerators = accelerators.collect::<Vec<_>>();
+        for accelerator in &alternate_accelerators {
+            accelerator.key_equivalent()?;
+        }
+
+        self.set_accelerator(accelerator)?;
+        self.alternate_accelerators = alternate_accelerators;
+        Ok(())
+    }
+
     pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
         let key_equivalent = (accelerator)
             .as_ref()
@@ -381,9 +410,21 @@ impl MenuChild {
         }
 
         self.accelerator = accelerator;
+        self.alternate_accelerators.clear();
 
         Ok(())
     }
+
+    pub fn set_accelerator_sequence(
+        &mut self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        // macOS menus can only display single key accelerators
+        let accelerator = sequence
+            .filter(|s| s.accelerators().len() == 1)
+            .map(|s| s.accelerators()[0]);
+        self.set_accelerator(accelerator)
+    }
 }
 
 /// CheckMenuItem methods
@@ -394,14 +435,38 @@ impl MenuChild {
 
     pub fn set_checked(&mut self, checked: bool) {
         self.checked = checked;
+        self.mixed = false;
+        self.update_ns_items_state();
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.mixed
+    }
+
+    pub fn set_mixed(&mut self, mixed: bool) {
+        self.mixed = mixed;
+        self.update_ns_items_state();
+    }
+
+    fn update_ns_items_state(&self) {
+        let state = self.ns_state();
         for ns_items in self.ns_menu_items.values() {
             for &ns_item in ns_items {
                 unsafe {
-                    let () = msg_send![ns_item, setState: checked as u32];
+                    let () = msg_send![ns_item, setState: state];
                 }
             }
         }
     }
+
+    /// The `NSControlStateValue` for the state of this item.
+    fn ns_state(&self) -> isize {
+        if self.mixed {
+            -1
+        } else {
+            self.checked as isize
+        }
+    }
 }
 
 /// IconMenuItem methods
@@ -548,6 +613,10 @@ impl MenuChild {
             if !self.enabled {
                 let () = msg_send![ns_menu_item, setEnabled: NO];
             }
+
+            if self.icon.is_some() {
+                menuitem_set_icon(ns_menu_item, self.icon.as_ref());
+            }
         }
 
         for item in self.children.as_ref().unwrap() {
@@ -654,8 +723,12 @@ impl MenuChild {
             if !self.enabled {
                 let () = msg_send![ns_menu_item, setEnabled: NO];
             }
-            if self.checked {
-                let () = msg_send![ns_menu_item, setState: 1_isize];
+            if self.checked || self.mixed {
+                let () = msg_send![ns_menu_item, setState: self.ns_state()];
+            }
+
+            if self.icon.is_some() {
+                menuitem_set_icon(ns_menu_item, self.icon.as_ref());
             }
         }
 
@@ -899,6 +972,9 @@ fn menuitem_set_icon(menuitem: id, icon: Option<&Icon>) {
     if let Some(icon) = icon {
         unsafe {
             let nsimage = icon.inner.to_nsimage(Some(18.));
+            if icon.is_template {
+                let _: () = msg_send![nsimage, setTemplate: YES];
+            }
             let _: () = msg_send![menuitem, setImage: nsimage];
         }
     } else {
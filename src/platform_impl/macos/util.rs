@@ -9,17 +9,6 @@ use cocoa::{
     foundation::NSString,
 };
 
-/// Strips single `&` characters from the string.
-///
-/// `&` can be escaped as `&&` to prevent stripping, in which case a single `&` will be output.
-pub fn strip_mnemonic<S: AsRef<str>>(string: S) -> String {
-    string
-        .as_ref()
-        .replace("&&", "[~~]")
-        .replace('&', "")
-        .replace("[~~]", "&")
-}
-
 /// Copies the contents of the NSString into a `String` which gets returned.
 pub(crate) unsafe fn ns_string_to_rust(ns_string: id) -> String {
     let slice = slice::from_raw_parts(ns_string.UTF8String() as *mut u8, ns_string.len());
@@ -5,19 +5,25 @@
 #[cfg(target_os = "windows")]
 #[path = "windows/mod.rs"]
 mod platform;
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(feature = "gtk4")))]
 #[path = "gtk/mod.rs"]
 mod platform;
+#[cfg(all(target_os = "linux", feature = "gtk4"))]
+#[path = "gtk4/mod.rs"]
+mod platform;
 #[cfg(target_os = "macos")]
 #[path = "macos/mod.rs"]
 mod platform;
 
+#[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+pub(crate) mod x11_hotkeys;
+
 use std::{
     cell::{Ref, RefCell, RefMut},
     rc::Rc,
 };
 
-use crate::{items::*, IsMenuItem, MenuItemKind, MenuItemType};
+use crate::{accelerator::Accelerator, items::*, IsMenuItem, MenuItemKind, MenuItemType};
 
 pub(crate) use self::platform::*;
 
@@ -78,4 +84,23 @@ impl MenuItemKind {
             MenuItemKind::Icon(i) => i.0.borrow_mut(),
         }
     }
+
+    /// Changes the accelerators of this item with `set` once `accelerators`, the new
+    /// ones triggering it, pass the strict menus check, then grabs them again if they
+    /// are global hotkeys.
+    pub(crate) fn set_accelerators_with(
+        &self,
+        accelerators: &[Accelerator],
+        set: impl FnOnce(&mut MenuChild) -> crate::Result<()>,
+    ) -> crate::Result<()> {
+        let id = self.child().id();
+        crate::menu::check_strict_accelerator(id, accelerators)?;
+        set(&mut self.child_mut())?;
+        #[cfg(all(target_os = "linux", feature = "x11-global-hotkeys"))]
+        {
+            let accelerators = self.child().accelerators();
+            x11_hotkeys::update(id, &accelerators)?;
+        }
+        Ok(())
+    }
 }
@@ -2,8 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::fmt;
-
 use keyboard_types::{Code, Modifiers};
 use windows_sys::Win32::UI::{
     Input::KeyboardAndMouse::*,
@@ -17,17 +15,28 @@ impl Accelerator {
     pub fn to_accel(&self, menu_id: u16) -> crate::Result<ACCEL> {
         let mut virt_key = FVIRTKEY;
         let key_mods: Modifiers = self.mods;
+        let unsupported = key_mods & (Modifiers::HYPER | Modifiers::META);
+        if !unsupported.is_empty() {
+            return Err(crate::Error::UnsupportedAcceleratorModifiers(unsupported));
+        }
         if key_mods.contains(Modifiers::CONTROL) {
             virt_key |= FCONTROL;
         }
         if key_mods.contains(Modifiers::ALT) {
             virt_key |= FALT;
         }
+        // AltGr is reported as Ctrl+Alt
+        if key_mods.contains(Modifiers::ALT_GRAPH) {
+            virt_key |= FCONTROL | FALT;
+        }
         if key_mods.contains(Modifiers::SHIFT) {
             virt_key |= FSHIFT;
         }
 
-        let vk_code = key_to_vk(&self.key)?;
+        let vk_code = match self.logical {
+            Some(c) => char_to_vk(c)?,
+            None => key_to_vk(&self.key)?,
+        };
         let mod_code = vk_code >> 8;
         if mod_code & 0x1 != 0 {
             virt_key |= FSHIFT;
@@ -48,6 +57,20 @@ impl Accelerator {
     }
 }
 
+// looks up the key producing `c` in the current keyboard layout,
+// with the modifiers it requires in the high byte
+fn char_to_vk(c: char) -> crate::Result<VIRTUAL_KEY> {
+    let mut utf16 = [0; 2];
+    let vk = match c.encode_utf16(&mut utf16) {
+        [c] => unsafe { VkKeyScanW(*c) },
+        _ => -1,
+    };
+    if vk == -1 {
+        return Err(crate::Error::UnrecognizedAcceleratorCode(c.to_string()));
+    }
+    Ok(vk as u16)
+}
+
 // used to build accelerators table from Key
 fn key_to_vk(key: &Code) -> crate::Result<VIRTUAL_KEY> {
     Ok(match key {
@@ -162,86 +185,55 @@ fn key_to_vk(key: &Code) -> crate::Result<VIRTUAL_KEY> {
         Code::MediaPlayPause => VK_MEDIA_PLAY_PAUSE,
         Code::LaunchMail => VK_LAUNCH_MAIL,
         Code::Convert => VK_CONVERT,
+        Code::Numpad0 => VK_NUMPAD0,
+        Code::Numpad1 => VK_NUMPAD1,
+        Code::Numpad2 => VK_NUMPAD2,
+        Code::Numpad3 => VK_NUMPAD3,
+        Code::Numpad4 => VK_NUMPAD4,
+        Code::Numpad5 => VK_NUMPAD5,
+        Code::Numpad6 => VK_NUMPAD6,
+        Code::Numpad7 => VK_NUMPAD7,
+        Code::Numpad8 => VK_NUMPAD8,
+        Code::Numpad9 => VK_NUMPAD9,
+        Code::NumpadAdd => VK_ADD,
+        Code::NumpadComma => VK_SEPARATOR,
+        Code::NumpadDecimal => VK_DECIMAL,
+        Code::NumpadDivide => VK_DIVIDE,
+        // Windows doesn't distinguish both enter keys in accelerator tables.
+        Code::NumpadEnter => VK_RETURN,
+        Code::NumpadEqual => VK_OEM_NEC_EQUAL,
+        Code::NumpadMultiply => VK_MULTIPLY,
+        Code::NumpadSubtract => VK_SUBTRACT,
+        Code::MediaSelect => VK_LAUNCH_MEDIA_SELECT,
+        Code::LaunchApp1 => VK_LAUNCH_APP1,
+        Code::LaunchApp2 => VK_LAUNCH_APP2,
+        Code::IntlBackslash => VK_OEM_102,
+        Code::IntlRo => VK_ABNT_C1,
+        // only layouts with a yen key type `¥`, elsewhere its key code is shared with other keys
+        Code::IntlYen => match unsafe { VkKeyScanW('¥' as u16) } {
+            -1 => {
+                return Err(crate::Error::UnrecognizedAcceleratorCode(
+                    Code::IntlYen.to_string(),
+                ))
+            }
+            vk => vk as u16,
+        },
+        Code::Lang1 => VK_HANGUL,
+        Code::Lang2 => VK_HANJA,
+        Code::Lang3 => VK_DBE_KATAKANA,
+        Code::Lang4 => VK_DBE_HIRAGANA,
+        Code::Lang5 => VK_DBE_DBCSCHAR,
         key => return Err(crate::Error::UnrecognizedAcceleratorCode(key.to_string())),
     })
 }
 
-impl fmt::Display for Accelerator {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let key_mods: Modifiers = self.mods;
-        if key_mods.contains(Modifiers::CONTROL) {
-            write!(f, "Ctrl+")?;
-        }
-        if key_mods.contains(Modifiers::SHIFT) {
-            write!(f, "Shift+")?;
-        }
-        if key_mods.contains(Modifiers::ALT) {
-            write!(f, "Alt+")?;
-        }
-        if key_mods.contains(Modifiers::SUPER) {
-            write!(f, "Windows+")?;
-        }
-        match &self.key {
-            Code::KeyA => write!(f, "A"),
-            Code::KeyB => write!(f, "B"),
-            Code::KeyC => write!(f, "C"),
-            Code::KeyD => write!(f, "D"),
-            Code::KeyE => write!(f, "E"),
-            Code::KeyF => write!(f, "F"),
-            Code::KeyG => write!(f, "G"),
-            Code::KeyH => write!(f, "H"),
-            Code::KeyI => write!(f, "I"),
-            Code::KeyJ => write!(f, "J"),
-            Code::KeyK => write!(f, "K"),
-            Code::KeyL => write!(f, "L"),
-            Code::KeyM => write!(f, "M"),
-            Code::KeyN => write!(f, "N"),
-            Code::KeyO => write!(f, "O"),
-            Code::KeyP => write!(f, "P"),
-            Code::KeyQ => write!(f, "Q"),
-            Code::KeyR => write!(f, "R"),
-            Code::KeyS => write!(f, "S"),
-            Code::KeyT => write!(f, "T"),
-            Code::KeyU => write!(f, "U"),
-            Code::KeyV => write!(f, "V"),
-            Code::KeyW => write!(f, "W"),
-            Code::KeyX => write!(f, "X"),
-            Code::KeyY => write!(f, "Y"),
-            Code::KeyZ => write!(f, "Z"),
-            Code::Digit0 => write!(f, "0"),
-            Code::Digit1 => write!(f, "1"),
-            Code::Digit2 => write!(f, "2"),
-            Code::Digit3 => write!(f, "3"),
-            Code::Digit4 => write!(f, "4"),
-            Code::Digit5 => write!(f, "5"),
-            Code::Digit6 => write!(f, "6"),
-            Code::Digit7 => write!(f, "7"),
-            Code::Digit8 => write!(f, "8"),
-            Code::Digit9 => write!(f, "9"),
-            Code::Comma => write!(f, ","),
-            Code::Minus => write!(f, "-"),
-            Code::Period => write!(f, "."),
-            Code::Space => write!(f, "Space"),
-            Code::Equal => write!(f, "="),
-            Code::Semicolon => write!(f, ";"),
-            Code::Slash => write!(f, "/"),
-            Code::Backslash => write!(f, "\\"),
-            Code::Quote => write!(f, "\'"),
-            Code::Backquote => write!(f, "`"),
-            Code::BracketLeft => write!(f, "["),
-            Code::BracketRight => write!(f, "]"),
-            Code::Tab => write!(f, "Tab"),
-            Code::Escape => write!(f, "Esc"),
-            Code::Delete => write!(f, "Del"),
-            Code::Insert => write!(f, "Ins"),
-            Code::PageUp => write!(f, "PgUp"),
-            Code::PageDown => write!(f, "PgDn"),
-            // These names match LibreOffice.
-            Code::ArrowLeft => write!(f, "Left"),
-            Code::ArrowRight => write!(f, "Right"),
-            Code::ArrowUp => write!(f, "Up"),
-            Code::ArrowDown => write!(f, "Down"),
-            _ => write!(f, "{:?}", self.key),
+#[test]
+fn test_key_to_vk_codes() {
+    for code in crate::accelerator::PARSEABLE_CODES {
+        // depends on the keyboard layout of the machine running the test
+        if *code == Code::IntlYen {
+            continue;
         }
+        assert!(key_to_vk(code).is_ok(), "{code} isn't mapped");
     }
 }
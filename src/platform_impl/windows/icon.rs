@@ -70,12 +70,25 @@ struct RaiiIcon {
 #[derive(Clone)]
 pub(crate) struct WinIcon {
     inner: Arc<RaiiIcon>,
+    #[cfg(feature = "svg")]
+    svg: Option<SvgIcon>,
 }
 
 unsafe impl Send for WinIcon {}
 
 impl WinIcon {
     pub unsafe fn to_hbitmap(&self) -> HBITMAP {
+        // draw SVG icons rasterized at the exact size rather than a scaled down HICON
+        #[cfg(feature = "svg")]
+        let rasterized = self
+            .svg
+            .as_ref()
+            .and_then(|svg| svg.rasterize(16, 16).into_windows_icon().ok());
+        #[cfg(feature = "svg")]
+        let handle = rasterized.as_ref().unwrap_or(self).inner.handle;
+        #[cfg(not(feature = "svg"))]
+        let handle = self.inner.handle;
+
         let hdc = CreateCompatibleDC(0);
 
         let rc = RECT {
@@ -101,17 +114,7 @@ impl WinIcon {
 
         let h_bitmap_old = SelectObject(hdc, hbitmap);
 
-        DrawIconEx(
-            hdc,
-            0,
-            0,
-            self.inner.handle,
-            rc.right,
-            rc.bottom,
-            0,
-            0,
-            DI_NORMAL,
-        );
+        DrawIconEx(hdc, 0, 0, handle, rc.right, rc.bottom, 0, 0, DI_NORMAL);
 
         SelectObject(hdc, h_bitmap_old);
         DeleteDC(hdc);
@@ -124,9 +127,19 @@ impl WinIcon {
         rgba_icon.into_windows_icon()
     }
 
+    #[cfg(feature = "svg")]
+    pub(crate) fn from_svg(svg: SvgIcon) -> Result<Self, BadIcon> {
+        let (width, height) = svg.size();
+        let mut icon = svg.rasterize(width, height).into_windows_icon()?;
+        icon.svg = Some(svg);
+        Ok(icon)
+    }
+
     fn from_handle(handle: HICON) -> Self {
         Self {
             inner: Arc::new(RaiiIcon { handle }),
+            #[cfg(feature = "svg")]
+            svg: None,
         }
     }
 
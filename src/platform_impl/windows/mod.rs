@@ -9,7 +9,7 @@ mod util;
 pub(crate) use self::icon::WinIcon as PlatformIcon;
 
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, AcceleratorSequence, DisplayStyle},
     icon::{Icon, NativeIcon},
     items::PredefinedMenuItemType,
     util::{AddOp, Counter},
@@ -43,7 +43,7 @@ use windows_sys::Win32::{
 
 static COUNTER: Counter = Counter::new_with_start(1000);
 
-type AccelWrapper = (HACCEL, HashMap<u32, Accel>);
+type AccelWrapper = (HACCEL, HashMap<u32, Vec<Accel>>);
 
 macro_rules! inner_menu_child_and_flags {
     ($item:ident) => {{
@@ -137,16 +137,17 @@ impl Menu {
 
             let mut text = child_.text.clone();
 
-            if let Some(accelerator) = &child_.accelerator {
-                let accel_str = accelerator.to_string();
-
+            if let Some(accel_str) = child_.accelerator_label() {
                 text.push('\t');
                 text.push_str(&accel_str);
+            }
 
+            let accelerators = child_.accelerators();
+            if !accelerators.is_empty() {
                 AccelAction::add(
                     &mut self.haccel_store.borrow_mut(),
                     child_.id(),
-                    accelerator,
+                    &accelerators,
                 )?;
             }
 
@@ -377,12 +378,18 @@ pub(crate) struct MenuChild {
     // menu item fields
     id: u32,
     accelerator: Option<Accelerator>,
+    /// accelerators registered in addition to `accelerator` but not displayed
+    alternate_accelerators: Vec<Accelerator>,
+    /// a multi-key sequence, only displayed as Windows menus can't handle it
+    accelerator_sequence: Option<AcceleratorSequence>,
 
     // predefined menu item fields
     predefined_item_type: PredefinedMenuItemType,
 
     // check menu item fields
     checked: bool,
+    /// Windows menus have no mixed state, so it is only kept track of.
+    mixed: bool,
 
     // icon menu item fields
     icon: Option<Icon>,
@@ -531,16 +538,16 @@ impl MenuChild {
     }
 
     pub fn set_text(&mut self, text: &str) {
-        self.text = if let Some(accelerator) = self.accelerator {
-            format!("{text}\t{}", accelerator)
-        } else {
-            text.to_string()
+        self.text = text.to_string();
+        let mut label = match self.accelerator_label() {
+            Some(accel_str) => encode_wide(format!("{text}\t{accel_str}")),
+            None => encode_wide(text),
         };
         for parent in &self.parents_hemnu {
             let mut info: MENUITEMINFOW = unsafe { std::mem::zeroed() };
             info.cbSize = std::mem::size_of::<MENUITEMINFOW>() as _;
             info.fMask = MIIM_STRING;
-            info.dwTypeData = encode_wide(&self.text).as_mut_ptr();
+            info.dwTypeData = label.as_mut_ptr();
 
             unsafe { SetMenuItemInfoW(*parent, self.id(), false.into(), &info) };
         }
@@ -574,22 +581,70 @@ impl MenuChild {
         }
     }
 
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn accelerators(&self) -> Vec<Accelerator> {
+        self.accelerator
+            .into_iter()
+            .chain(self.alternate_accelerators.iter().copied())
+            .collect()
+    }
+
     pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
-        self.accelerator = accelerator;
+        self.set_accelerators(accelerator.into_iter().collect())
+    }
+
+    pub fn set_accelerators(&mut self, accelerators: Vec<Accelerator>) -> crate::Result<()> {
+        for accelerator in &accelerators {
+            accelerator.to_accel(self.id as _)?;
+        }
+
+        let mut accelerators = accelerators.into_iter();
+        self.accelerator = accelerators.next();
+        self.alternate_accelerators = accelerators.collect();
+        self.accelerator_sequence = None;
         self.set_text(&self.text.clone());
 
+        let accelerators = self.accelerators();
         let haccel_stores = self.root_menu_haccel_stores.as_mut().unwrap();
         for store in haccel_stores {
             let mut store = store.borrow_mut();
-            if let Some(accelerator) = self.accelerator {
-                AccelAction::add(&mut store, self.id, &accelerator)?
-            } else {
+            if accelerators.is_empty() {
                 AccelAction::remove(&mut store, self.id)
+            } else {
+                AccelAction::add(&mut store, self.id, &accelerators)?
             }
         }
 
         Ok(())
     }
+
+    pub fn set_accelerator_sequence(
+        &mut self,
+        sequence: Option<AcceleratorSequence>,
+    ) -> crate::Result<()> {
+        match sequence {
+            Some(sequence) if sequence.accelerators().len() == 1 => {
+                self.set_accelerator(Some(sequence.accelerators()[0]))
+            }
+            sequence => {
+                self.set_accelerator(None)?;
+                self.accelerator_sequence = sequence;
+                self.set_text(&self.text.clone());
+                Ok(())
+            }
+        }
+    }
+
+    fn accelerator_label(&self) -> Option<String> {
+        match (&self.accelerator, &self.accelerator_sequence) {
+            (Some(accelerator), _) => Some(accelerator.to_display_string(DisplayStyle::Windows)),
+            (None, Some(sequence)) => Some(sequence.to_display_string(DisplayStyle::Windows)),
+            (None, None) => None,
+        }
+    }
 }
 
 /// CheckMenuItem methods
@@ -613,6 +668,7 @@ impl MenuChild {
         use windows_sys::Win32::UI::WindowsAndMessaging;
 
         self.checked = checked;
+        self.mixed = false;
         for parent in &self.parents_hemnu {
             unsafe {
                 WindowsAndMessaging::CheckMenuItem(
@@ -623,6 +679,14 @@ impl MenuChild {
             };
         }
     }
+
+    pub fn is_mixed(&self) -> bool {
+        self.mixed
+    }
+
+    pub fn set_mixed(&mut self, mixed: bool) {
+        self.mixed = mixed;
+    }
 }
 
 /// IconMenuItem methods
@@ -664,15 +728,16 @@ impl MenuChild {
 
             let mut text = child_.text.clone();
 
-            if let Some(accelerator) = &child_.accelerator {
-                let accel_str = accelerator.to_string();
-
+            if let Some(accel_str) = child_.accelerator_label() {
                 text.push('\t');
                 text.push_str(&accel_str);
+            }
 
+            let accelerators = child_.accelerators();
+            if !accelerators.is_empty() {
                 for root_menu in self.root_menu_haccel_stores.as_mut().unwrap() {
                     let mut haccel = root_menu.borrow_mut();
-                    AccelAction::add(&mut haccel, child_.id(), accelerator)?;
+                    AccelAction::add(&mut haccel, child_.id(), &accelerators)?;
                 }
             }
 
@@ -856,10 +921,13 @@ impl AccelAction {
     fn add(
         haccel_store: &mut RefMut<AccelWrapper>,
         id: u32,
-        accelerator: &Accelerator,
+        accelerators: &[Accelerator],
     ) -> crate::Result<()> {
-        let accel = accelerator.to_accel(id as _)?;
-        haccel_store.1.insert(id, Accel(accel));
+        let accels = accelerators
+            .iter()
+            .map(|a| a.to_accel(id as _).map(Accel))
+            .collect::<crate::Result<Vec<_>>>()?;
+        haccel_store.1.insert(id, accels);
 
         Self::update_store(haccel_store);
 
@@ -875,15 +943,13 @@ impl AccelAction {
     fn update_store(haccel_store: &mut RefMut<AccelWrapper>) {
         unsafe {
             DestroyAcceleratorTable(haccel_store.0);
-            haccel_store.0 = CreateAcceleratorTableW(
-                haccel_store
-                    .1
-                    .values()
-                    .map(|i| i.0)
-                    .collect::<Vec<_>>()
-                    .as_ptr(),
-                haccel_store.1.len() as _,
-            );
+            let accels = haccel_store
+                .1
+                .values()
+                .flatten()
+                .map(|i| i.0)
+                .collect::<Vec<_>>();
+            haccel_store.0 = CreateAcceleratorTableW(accels.as_ptr(), accels.len() as _);
         }
     }
 }
@@ -0,0 +1,577 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Global hotkeys for menu items on X11.
+//!
+//! The keys are grabbed with `XGrabKey` on the root window by a dedicated thread
+//! owning its own display connection, so it works whichever toolkit and event loop
+//! the application uses and whether or not one of its windows is focused.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    mem,
+    os::raw::{c_int, c_uchar, c_uint},
+    ptr,
+    rc::{Rc, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use keyboard_types::Modifiers;
+use once_cell::sync::Lazy;
+use x11_dl::{keysym, xlib};
+
+use super::MenuChild;
+use crate::{accelerator::Accelerator, IsMenuItem, MenuEvent, MenuItemKind, Submenu};
+
+/// Lock and Num Lock are ignored when matching hotkeys, so every grab
+/// is repeated with each combination of them.
+const IGNORED_MASKS: [c_uint; 4] = [
+    0,
+    xlib::LockMask,
+    xlib::Mod2Mask,
+    xlib::LockMask | xlib::Mod2Mask,
+];
+
+/// The major opcode of the `GrabKey` request.
+const X_GRAB_KEY: c_uchar = 33;
+
+static COMMANDS: Lazy<Mutex<Option<Sender<Command>>>> = Lazy::new(|| Mutex::new(None));
+/// The menu items with global hotkeys, and whether they are enabled.
+static REGISTERED: Lazy<Mutex<HashMap<u32, bool>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+/// Set by [`on_access_error`], which only handles the errors of the hotkeys connection.
+static GRAB_FAILED: AtomicBool = AtomicBool::new(false);
+
+enum Command {
+    Register {
+        id: u32,
+        hotkeys: Vec<(Accelerator, u32)>,
+        reply: Sender<crate::Result<()>>,
+    },
+    Unregister(u32),
+}
+
+/// Grabs the `accelerators` of the menu item `id`, replacing its previous ones.
+///
+/// They only emit events while the item is `enabled`.
+pub(crate) fn register(id: u32, accelerators: &[Accelerator], enabled: bool) -> crate::Result<()> {
+    let hotkeys = accelerators
+        .iter()
+        .map(|a| super::accelerator_keysym(a).map(|keysym| (*a, keysym)))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let (reply, result) = crossbeam_channel::bounded(1);
+    send(Command::Register { id, hotkeys, reply })?;
+    result
+        .recv()
+        .map_err(|e| crate::Error::X11ConnectionFailed(e.to_string()))??;
+
+    REGISTERED.lock().unwrap().insert(id, enabled);
+    Ok(())
+}
+
+/// Ungrabs the accelerators of the menu item `id`, if they were grabbed.
+pub(crate) fn unregister(id: u32) {
+    if REGISTERED.lock().unwrap().remove(&id).is_some() {
+        let _ = send(Command::Unregister(id));
+    }
+}
+
+/// Registers the accelerators of `item` as global hotkeys, or unregisters them.
+pub(crate) fn set_global_hotkey(item: &dyn IsMenuItem, global: bool) -> crate::Result<()> {
+    let child = item.child();
+    let id = item.id();
+    if global {
+        let (accelerators, enabled) = {
+            let child = child.borrow();
+            (child.accelerators(), child.is_enabled())
+        };
+        register(id, &accelerators, enabled)?;
+        child.borrow_mut().global_hotkey.registered = Some(id);
+    } else {
+        unregister(id);
+        child.borrow_mut().global_hotkey.registered = None;
+    }
+    Ok(())
+}
+
+/// Records that `item` was added to `submenu`, or to a menu when `None`.
+pub(crate) fn item_added(item: &dyn IsMenuItem, submenu: Option<&Submenu>) {
+    item.child()
+        .borrow_mut()
+        .global_hotkey
+        .parents
+        .push(submenu.map(|s| Rc::downgrade(&s.0)));
+}
+
+/// Records that `item` was removed from `submenu`, or from a menu when `None`, and
+/// ungrabs the accelerators of it or, for a submenu, of its descendants that can't be
+/// reached from a menu anymore.
+pub(crate) fn item_removed(item: &dyn IsMenuItem, submenu: Option<&Submenu>) {
+    {
+        let child = item.child();
+        let parents = &mut child.borrow_mut().global_hotkey.parents;
+        let index = parents.iter().position(|p| match (p, submenu) {
+            (Some(p), Some(s)) => Weak::ptr_eq(p, &Rc::downgrade(&s.0)),
+            (None, None) => true,
+            _ => false,
+        });
+        if let Some(index) = index {
+            parents.remove(index);
+        }
+    }
+    unregister_detached(item);
+}
+
+fn unregister_detached(item: &dyn IsMenuItem) {
+    if is_attached(&item.child()) {
+        return;
+    }
+    match item.kind() {
+        MenuItemKind::Submenu(submenu) => {
+            for item in submenu.items() {
+                unregister_detached(item.as_ref());
+            }
+        }
+        _ => unregister(item.id()),
+    }
+}
+
+/// Returns whether `child` is in a menu, directly or through submenus.
+fn is_attached(child: &Rc<RefCell<MenuChild>>) -> bool {
+    child
+        .borrow()
+        .global_hotkey
+        .parents
+        .iter()
+        .any(|p| match p {
+            Some(submenu) => submenu.upgrade().is_some_and(|s| is_attached(&s)),
+            None => true,
+        })
+}
+
+pub(crate) fn is_registered(id: u32) -> bool {
+    REGISTERED.lock().unwrap().contains_key(&id)
+}
+
+/// Grabs the new `accelerators` of the menu item `id` if it has global hotkeys.
+pub(crate) fn update(id: u32, accelerators: &[Accelerator]) -> crate::Result<()> {
+    let enabled = REGISTERED.lock().unwrap().get(&id).copied();
+    match enabled {
+        Some(enabled) => register(id, accelerators, enabled),
+        None => Ok(()),
+    }
+}
+
+/// Stops or resumes emitting events for the global hotkeys of the menu item `id`,
+/// they stay grabbed meanwhile so no other application takes them.
+pub(crate) fn set_enabled(id: u32, enabled: bool) {
+    if let Some(registered) = REGISTERED.lock().unwrap().get_mut(&id) {
+        *registered = enabled;
+    }
+}
+
+fn is_enabled(id: u32) -> bool {
+    REGISTERED.lock().unwrap().get(&id) == Some(&true)
+}
+
+/// The global hotkey state of a menu item.
+///
+/// It unregisters the hotkeys when the item is dropped without being removed from its menu.
+#[derive(Debug, Default)]
+pub(crate) struct HotkeyGuard {
+    /// The id of the item, once it registered global hotkeys.
+    pub(crate) registered: Option<u32>,
+    /// The submenus the item was added to, `None` for a menu.
+    pub(crate) parents: Vec<Option<Weak<RefCell<MenuChild>>>>,
+}
+
+impl Drop for HotkeyGuard {
+    fn drop(&mut self) {
+        if let Some(id) = self.registered {
+            unregister(id);
+        }
+    }
+}
+
+fn send(command: Command) -> crate::Result<()> {
+    let mut commands = COMMANDS.lock().unwrap();
+    if commands.is_none() {
+        *commands = Some(spawn()?);
+    }
+    commands
+        .as_ref()
+        .unwrap()
+        .send(command)
+        .map_err(|e| crate::Error::X11ConnectionFailed(e.to_string()))
+}
+
+fn spawn() -> crate::Result<Sender<Command>> {
+    let (sender, commands) = crossbeam_channel::unbounded();
+    let (started, result) = crossbeam_channel::bounded(1);
+
+    thread::Builder::new()
+        .name("muda-x11-hotkeys".into())
+        .spawn(move || match X11Hotkeys::open() {
+            Ok(mut hotkeys) => {
+                let _ = started.send(Ok(()));
+                hotkeys.run(commands);
+            }
+            Err(e) => {
+                let _ = started.send(Err(e));
+            }
+        })
+        .map_err(|e| crate::Error::X11ConnectionFailed(e.to_string()))?;
+
+    result
+        .recv()
+        .map_err(|e| crate::Error::X11ConnectionFailed(e.to_string()))??;
+    Ok(sender)
+}
+
+struct Grab {
+    keycode: u8,
+    mask: c_uint,
+}
+
+struct X11Hotkeys {
+    xlib: xlib::Xlib,
+    display: *mut xlib::Display,
+    root: xlib::Window,
+    grabs: HashMap<u32, Vec<Grab>>,
+}
+
+impl X11Hotkeys {
+    fn open() -> crate::Result<Self> {
+        let xlib =
+            xlib::Xlib::open().map_err(|e| crate::Error::X11ConnectionFailed(e.to_string()))?;
+        // Xlib is used from this thread and the toolkit's one, this is a no-op if the
+        // toolkit or libX11 itself (since 1.8) already initialized its thread support
+        unsafe { (xlib.XInitThreads)() };
+        let display = unsafe { (xlib.XOpenDisplay)(ptr::null()) };
+        if display.is_null() {
+            return Err(crate::Error::X11ConnectionFailed(
+                "couldn't open the display".into(),
+            ));
+        }
+        // unlike `XSetErrorHandler`, which is process-wide and would race with the
+        // toolkit's handler, this only intercepts the `BadAccess` errors of `display`
+        unsafe { (xlib.XESetWireToError)(display, xlib::BadAccess.into(), Some(on_access_error)) };
+        let root = unsafe { (xlib.XDefaultRootWindow)(display) };
+
+        Ok(Self {
+            xlib,
+            display,
+            root,
+            grabs: HashMap::new(),
+        })
+    }
+
+    fn run(&mut self, commands: Receiver<Command>) {
+        loop {
+            match commands.recv_timeout(Duration::from_millis(50)) {
+                Ok(Command::Register { id, hotkeys, reply }) => {
+                    let _ = reply.send(self.register(id, &hotkeys));
+                }
+                Ok(Command::Unregister(id)) => self.unregister(id),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            while unsafe { (self.xlib.XPending)(self.display) } > 0 {
+                let mut event: xlib::XEvent = unsafe { mem::zeroed() };
+                unsafe { (self.xlib.XNextEvent)(self.display, &mut event) };
+                if event.get_type() != xlib::KeyPress {
+                    continue;
+                }
+
+                let key = unsafe { event.key };
+                let mask = key.state & !(xlib::LockMask | xlib::Mod2Mask);
+                let id = self.grabs.iter().find_map(|(id, grabs)| {
+                    grabs
+                        .iter()
+                        .any(|g| c_uint::from(g.keycode) == key.keycode && g.mask == mask)
+                        .then_some(*id)
+                });
+                if let Some(id) = id.filter(|id| is_enabled(*id)) {
+                    MenuEvent::send(MenuEvent { id });
+                }
+            }
+        }
+
+        for id in self.grabs.keys().copied().collect::<Vec<_>>() {
+            self.unregister(id);
+        }
+        unsafe { (self.xlib.XCloseDisplay)(self.display) };
+    }
+
+    fn register(&mut self, id: u32, hotkeys: &[(Accelerator, u32)]) -> crate::Result<()> {
+        self.unregister(id);
+
+        let mut grabs = Vec::new();
+        for (accelerator, keysym) in hotkeys {
+            match self.grab(accelerator, *keysym) {
+                Ok(grab) => grabs.push(grab),
+                Err(e) => {
+                    for grab in grabs {
+                        self.ungrab(&grab);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        self.grabs.insert(id, grabs);
+        Ok(())
+    }
+
+    fn unregister(&mut self, id: u32) {
+        for grab in self.grabs.remove(&id).unwrap_or_default() {
+            self.ungrab(&grab);
+        }
+    }
+
+    fn grab(&self, accelerator: &Accelerator, keysym: u32) -> crate::Result<Grab> {
+        let keycode = unsafe { (self.xlib.XKeysymToKeycode)(self.display, keysym.into()) };
+        if keycode == 0 {
+            return Err(crate::Error::UnrecognizedAcceleratorCode(
+                accelerator.to_string(),
+            ));
+        }
+        let grab = Grab {
+            keycode,
+            mask: modifiers_mask(accelerator.mods, |keysym| self.modifier_mask_of(keysym))?,
+        };
+
+        // a key grabbed by another client fails asynchronously with `BadAccess`,
+        // which `on_access_error` records once the server has processed the grabs
+        unsafe {
+            GRAB_FAILED.store(false, Ordering::SeqCst);
+            for ignored in IGNORED_MASKS {
+                (self.xlib.XGrabKey)(
+                    self.display,
+                    c_int::from(grab.keycode),
+                    grab.mask | ignored,
+                    self.root,
+                    xlib::False,
+                    xlib::GrabModeAsync,
+                    xlib::GrabModeAsync,
+                );
+            }
+            (self.xlib.XSync)(self.display, xlib::False);
+        }
+
+        if GRAB_FAILED.load(Ordering::SeqCst) {
+            self.ungrab(&grab);
+            return Err(crate::Error::GlobalHotKeyAlreadyGrabbed(*accelerator));
+        }
+        Ok(grab)
+    }
+
+    fn ungrab(&self, grab: &Grab) {
+        unsafe {
+            for ignored in IGNORED_MASKS {
+                (self.xlib.XUngrabKey)(
+                    self.display,
+                    c_int::from(grab.keycode),
+                    grab.mask | ignored,
+                    self.root,
+                );
+            }
+            (self.xlib.XSync)(self.display, xlib::False);
+        }
+    }
+
+    /// Returns the `ModN` mask that the key producing `keysym` is mapped to.
+    fn modifier_mask_of(&self, keysym: c_uint) -> Option<c_uint> {
+        unsafe {
+            let keycode = (self.xlib.XKeysymToKeycode)(self.display, keysym.into());
+            if keycode == 0 {
+                return None;
+            }
+            let map = (self.xlib.XGetModifierMapping)(self.display);
+            if map.is_null() {
+                return None;
+            }
+
+            let per_modifier = (*map).max_keypermod as usize;
+            let keycodes = std::slice::from_raw_parts((*map).modifiermap, 8 * per_modifier);
+            let index = keycodes.iter().position(|k| *k == keycode);
+            (self.xlib.XFreeModifiermap)(map);
+
+            index.map(|i| 1 << (i / per_modifier))
+        }
+    }
+}
+
+/// Returns the X11 modifier mask of `mods`, `mask_of` giving the `ModN` mask
+/// that the key producing a modifier keysym is mapped to.
+fn modifiers_mask(
+    mods: Modifiers,
+    mask_of: impl Fn(c_uint) -> Option<c_uint>,
+) -> crate::Result<c_uint> {
+    let mut mask = 0;
+    if mods.contains(Modifiers::SHIFT) {
+        mask |= xlib::ShiftMask;
+    }
+    if mods.contains(Modifiers::CONTROL) {
+        mask |= xlib::ControlMask;
+    }
+    if mods.contains(Modifiers::ALT) {
+        mask |= xlib::Mod1Mask;
+    }
+
+    // the other modifiers depend on the modifier mapping of the server
+    let mapped = [
+        (Modifiers::SUPER, keysym::XK_Super_L, Some(xlib::Mod4Mask)),
+        (
+            Modifiers::ALT_GRAPH,
+            keysym::XK_ISO_Level3_Shift,
+            Some(xlib::Mod5Mask),
+        ),
+        (Modifiers::HYPER, keysym::XK_Hyper_L, None),
+        (Modifiers::META, keysym::XK_Meta_L, None),
+    ];
+    for (modifier, keysym, fallback) in mapped {
+        if mods.contains(modifier) {
+            mask |= mask_of(keysym)
+                .or(fallback)
+                .ok_or(crate::Error::UnsupportedAcceleratorModifiers(modifier))?;
+        }
+    }
+
+    Ok(mask)
+}
+
+unsafe extern "C" fn on_access_error(
+    _display: *mut xlib::Display,
+    event: *mut xlib::XErrorEvent,
+    _wire: *mut xlib::xError,
+) -> c_int {
+    if (*event).request_code == X_GRAB_KEY {
+        GRAB_FAILED.store(true, Ordering::SeqCst);
+    }
+    // `0` tells Xlib not to report the error to the error handler
+    0
+}
+
+#[cfg(test)]
+fn register_for_test(id: u32, enabled: bool) {
+    REGISTERED.lock().unwrap().insert(id, enabled);
+}
+
+#[test]
+fn test_modifiers_mask() {
+    let unmapped = |_| None;
+    assert_eq!(
+        modifiers_mask(
+            Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT,
+            unmapped
+        )
+        .unwrap(),
+        xlib::ShiftMask | xlib::ControlMask | xlib::Mod1Mask
+    );
+    assert_eq!(
+        modifiers_mask(Modifiers::SUPER | Modifiers::ALT_GRAPH, unmapped).unwrap(),
+        xlib::Mod4Mask | xlib::Mod5Mask
+    );
+    assert!(matches!(
+        modifiers_mask(Modifiers::HYPER, unmapped),
+        Err(crate::Error::UnsupportedAcceleratorModifiers(
+            Modifiers::HYPER
+        ))
+    ));
+    assert!(matches!(
+        modifiers_mask(Modifiers::META, unmapped),
+        Err(crate::Error::UnsupportedAcceleratorModifiers(
+            Modifiers::META
+        ))
+    ));
+
+    let mapped = |keysym| match keysym {
+        keysym::XK_Super_L => Some(xlib::Mod3Mask),
+        keysym::XK_Hyper_L => Some(xlib::Mod4Mask),
+        keysym::XK_Meta_L => Some(xlib::Mod1Mask),
+        _ => None,
+    };
+    assert_eq!(
+        modifiers_mask(Modifiers::SUPER | Modifiers::HYPER, mapped).unwrap(),
+        xlib::Mod3Mask | xlib::Mod4Mask
+    );
+    assert_eq!(
+        modifiers_mask(Modifiers::META | Modifiers::SHIFT, mapped).unwrap(),
+        xlib::Mod1Mask | xlib::ShiftMask
+    );
+}
+
+#[test]
+fn test_registered() {
+    let id = u32::MAX - 1;
+    assert!(!is_registered(id));
+    // items without global hotkeys are left alone
+    update(id, &[]).unwrap();
+    set_enabled(id, true);
+    assert!(!is_registered(id));
+
+    register_for_test(id, true);
+    assert!(is_registered(id));
+    assert!(is_enabled(id));
+    set_enabled(id, false);
+    assert!(is_registered(id));
+    assert!(!is_enabled(id));
+    set_enabled(id, true);
+    assert!(is_enabled(id));
+
+    drop(HotkeyGuard {
+        registered: Some(id),
+        parents: Vec::new(),
+    });
+    assert!(!is_registered(id));
+    assert!(!is_enabled(id));
+}
+
+#[test]
+fn test_set_global_hotkey_off() {
+    let item = crate::MenuItem::new("Item", true, None);
+    item.0.borrow_mut().global_hotkey.registered = Some(item.id());
+    register_for_test(item.id(), true);
+    assert!(item.is_global_hotkey());
+
+    item.set_global_hotkey(false).unwrap();
+    assert!(!item.is_global_hotkey());
+    assert_eq!(item.0.borrow().global_hotkey.registered, None);
+}
+
+#[test]
+fn test_unregister_removed_items() {
+    let shared = crate::MenuItem::new("Shared", true, None);
+    let nested = crate::MenuItem::new("Nested", true, None);
+    let submenu = crate::Submenu::with_items("Submenu", true, &[&shared, &nested]).unwrap();
+    let menu = crate::Menu::with_items(&[&submenu, &shared]).unwrap();
+    register_for_test(shared.id(), true);
+    register_for_test(nested.id(), true);
+
+    // `shared` is still directly in `menu`
+    menu.remove(&submenu).unwrap();
+    assert!(is_registered(shared.id()));
+    assert!(!is_registered(nested.id()));
+
+    // the submenu it's also in isn't in a menu anymore
+    menu.remove(&shared).unwrap();
+    assert!(!is_registered(shared.id()));
+
+    // a removed item added back to another menu only loses its hotkeys once removed from it
+    register_for_test(nested.id(), true);
+    let other = crate::Menu::with_items(&[&nested]).unwrap();
+    submenu.remove(&nested).unwrap();
+    assert!(is_registered(nested.id()));
+    other.remove(&nested).unwrap();
+    assert!(!is_registered(nested.id()));
+}
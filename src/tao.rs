@@ -0,0 +1,167 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Conversions from [`tao`](::tao) keyboard events to accelerators.
+//!
+//! Requires the `tao` feature.
+//!
+//! ```no_run
+//! # use muda::{Menu, tao::AcceleratorHandler};
+//! # use tao::event::WindowEvent;
+//! // called in the event loop with a single handler created by `AcceleratorHandler::new()`
+//! fn on_window_event(handler: &mut AcceleratorHandler, menu: &Menu, event: &WindowEvent) {
+//!     if let Some(event) = handler.handle_window_event(menu, event) {
+//!         println!("{event:?}");
+//!     }
+//! }
+//! ```
+
+use ::tao::{
+    event::{ElementState, KeyEvent, WindowEvent},
+    keyboard::{Key as TaoKey, KeyCode, ModifiersState},
+};
+
+use crate::{
+    accelerator::{Accelerator, Code, Key, Modifiers},
+    Menu, MenuEvent,
+};
+
+macro_rules! same_codes {
+    ($key:expr; $($name:ident),* $(,)?) => {
+        match $key {
+            $(KeyCode::$name => Some(Code::$name),)*
+            _ => None,
+        }
+    };
+}
+
+/// Converts a tao [`KeyCode`] to the [`Code`] of the same physical key.
+pub fn code_from_key_code(key: KeyCode) -> Option<Code> {
+    match key {
+        KeyCode::SuperLeft => return Some(Code::MetaLeft),
+        KeyCode::SuperRight => return Some(Code::MetaRight),
+        _ => {}
+    }
+
+    same_codes!(key;
+        Backquote, Backslash, BracketLeft, BracketRight, Comma, Equal, Minus, Period, Quote,
+        Semicolon, Slash, IntlBackslash, IntlRo, IntlYen,
+        Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+        KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM,
+        KeyN, KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+        AltLeft, AltRight, ControlLeft, ControlRight, ShiftLeft, ShiftRight,
+        Backspace, CapsLock, ContextMenu, Enter, Space, Tab,
+        Convert, KanaMode, NonConvert, Lang1, Lang2, Lang3, Lang4, Lang5,
+        Delete, End, Help, Home, Insert, PageDown, PageUp,
+        ArrowDown, ArrowLeft, ArrowRight, ArrowUp,
+        NumLock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7,
+        Numpad8, Numpad9, NumpadAdd, NumpadComma, NumpadDecimal, NumpadDivide, NumpadEnter,
+        NumpadEqual, NumpadMultiply, NumpadSubtract,
+        Escape, Fn, FnLock, PrintScreen, ScrollLock, Pause,
+        BrowserBack, BrowserFavorites, BrowserForward, BrowserHome, BrowserRefresh,
+        BrowserSearch, BrowserStop, Eject, LaunchApp1, LaunchApp2, LaunchMail,
+        MediaPlayPause, MediaSelect, MediaStop, MediaTrackNext, MediaTrackPrevious,
+        Power, Sleep, AudioVolumeDown, AudioVolumeMute, AudioVolumeUp, WakeUp,
+        Again, Copy, Cut, Find, Open, Paste, Props, Select, Undo,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+        F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24,
+    )
+}
+
+/// Converts a tao [`ModifiersState`] to [`Modifiers`].
+pub fn modifiers_from_state(state: ModifiersState) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    if state.shift_key() {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if state.control_key() {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if state.alt_key() {
+        modifiers |= Modifiers::ALT;
+    }
+    if state.super_key() {
+        modifiers |= Modifiers::SUPER;
+    }
+    modifiers
+}
+
+/// Returns the [`Accelerator`] pressed by `event` while `modifiers` are held,
+/// or `None` for key releases and unknown keys.
+pub fn accelerator_from_key_event(
+    event: &KeyEvent,
+    modifiers: ModifiersState,
+) -> Option<Accelerator> {
+    if event.state != ElementState::Pressed {
+        return None;
+    }
+
+    let code = code_from_key_code(event.physical_key)?;
+    Some(Accelerator::new(
+        Some(modifiers_from_state(modifiers)),
+        code,
+    ))
+}
+
+/// Feeds tao window events to [`Menu::handle_key`], and to [`Menu::handle_logical_key`]
+/// for the accelerators created with [`Accelerator::new_logical`], so accelerators work
+/// even when the native menu of a window isn't focused or shown.
+///
+/// It keeps track of the modifiers from [`WindowEvent::ModifiersChanged`],
+/// so it should be given all the events of a window.
+#[derive(Debug, Default)]
+pub struct AcceleratorHandler {
+    modifiers: ModifiersState,
+}
+
+impl AcceleratorHandler {
+    /// Creates a new handler.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Activates the item of `menu` with the accelerator pressed in `event`,
+    /// and returns the emitted [`MenuEvent`].
+    pub fn handle_window_event(&mut self, menu: &Menu, event: &WindowEvent) -> Option<MenuEvent> {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = *modifiers;
+                None
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                let accelerator = accelerator_from_key_event(event, self.modifiers);
+                if let Some(event) = accelerator.and_then(|a| menu.handle_key(a.mods, a.key)) {
+                    return Some(event);
+                }
+
+                match &event.logical_key {
+                    TaoKey::Character(c) if event.state == ElementState::Pressed => menu
+                        .handle_logical_key(
+                            modifiers_from_state(self.modifiers),
+                            &Key::Character(c.to_string()),
+                        ),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_code_from_key_code() {
+    use ::tao::keyboard::NativeKeyCode;
+
+    assert_eq!(code_from_key_code(KeyCode::KeyA), Some(Code::KeyA));
+    assert_eq!(code_from_key_code(KeyCode::Enter), Some(Code::Enter));
+    assert_eq!(code_from_key_code(KeyCode::SuperLeft), Some(Code::MetaLeft));
+    assert_eq!(
+        code_from_key_code(KeyCode::Unidentified(NativeKeyCode::Unidentified)),
+        None
+    );
+    assert_eq!(
+        modifiers_from_state(ModifiersState::CONTROL | ModifiersState::SHIFT),
+        Modifiers::CONTROL | Modifiers::SHIFT
+    );
+}
@@ -10,6 +10,17 @@ pub enum AddOp {
     Insert(usize),
 }
 
+/// Strips single `&` characters from the string.
+///
+/// `&` can be escaped as `&&` to prevent stripping, in which case a single `&` will be output.
+pub fn strip_mnemonic<S: AsRef<str>>(string: S) -> String {
+    string
+        .as_ref()
+        .replace("&&", "[~~]")
+        .replace('&', "")
+        .replace("[~~]", "&")
+}
+
 pub struct Counter(AtomicU32);
 
 impl Counter {
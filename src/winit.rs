@@ -0,0 +1,324 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Conversions from [`winit`](::winit) keyboard events to accelerators.
+//!
+//! Requires the `winit` feature.
+//!
+//! ```no_run
+//! # use muda::{Menu, winit::AcceleratorHandler};
+//! # use winit::event::WindowEvent;
+//! // called in the event loop with a single handler created by `AcceleratorHandler::new()`
+//! fn on_window_event(handler: &mut AcceleratorHandler, menu: &Menu, event: &WindowEvent) {
+//!     if let Some(event) = handler.handle_window_event(menu, event) {
+//!         println!("{event:?}");
+//!     }
+//! }
+//! ```
+
+use ::winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent};
+
+use crate::{
+    accelerator::{Accelerator, Code, Key, Modifiers},
+    Menu, MenuEvent,
+};
+
+/// Converts a winit [`VirtualKeyCode`] to the [`Code`] of the same key on a US keyboard layout.
+pub fn code_from_virtual_keycode(key: VirtualKeyCode) -> Option<Code> {
+    use VirtualKeyCode::*;
+    let code = match key {
+        Key1 => Code::Digit1,
+        Key2 => Code::Digit2,
+        Key3 => Code::Digit3,
+        Key4 => Code::Digit4,
+        Key5 => Code::Digit5,
+        Key6 => Code::Digit6,
+        Key7 => Code::Digit7,
+        Key8 => Code::Digit8,
+        Key9 => Code::Digit9,
+        Key0 => Code::Digit0,
+        A => Code::KeyA,
+        B => Code::KeyB,
+        C => Code::KeyC,
+        D => Code::KeyD,
+        E => Code::KeyE,
+        F => Code::KeyF,
+        G => Code::KeyG,
+        H => Code::KeyH,
+        I => Code::KeyI,
+        J => Code::KeyJ,
+        K => Code::KeyK,
+        L => Code::KeyL,
+        M => Code::KeyM,
+        N => Code::KeyN,
+        O => Code::KeyO,
+        P => Code::KeyP,
+        Q => Code::KeyQ,
+        R => Code::KeyR,
+        S => Code::KeyS,
+        T => Code::KeyT,
+        U => Code::KeyU,
+        V => Code::KeyV,
+        W => Code::KeyW,
+        X => Code::KeyX,
+        Y => Code::KeyY,
+        Z => Code::KeyZ,
+        Escape => Code::Escape,
+        F1 => Code::F1,
+        F2 => Code::F2,
+        F3 => Code::F3,
+        F4 => Code::F4,
+        F5 => Code::F5,
+        F6 => Code::F6,
+        F7 => Code::F7,
+        F8 => Code::F8,
+        F9 => Code::F9,
+        F10 => Code::F10,
+        F11 => Code::F11,
+        F12 => Code::F12,
+        F13 => Code::F13,
+        F14 => Code::F14,
+        F15 => Code::F15,
+        F16 => Code::F16,
+        F17 => Code::F17,
+        F18 => Code::F18,
+        F19 => Code::F19,
+        F20 => Code::F20,
+        F21 => Code::F21,
+        F22 => Code::F22,
+        F23 => Code::F23,
+        F24 => Code::F24,
+        Snapshot => Code::PrintScreen,
+        Scroll => Code::ScrollLock,
+        Pause => Code::Pause,
+        Insert => Code::Insert,
+        Home => Code::Home,
+        Delete => Code::Delete,
+        End => Code::End,
+        PageDown => Code::PageDown,
+        PageUp => Code::PageUp,
+        Left => Code::ArrowLeft,
+        Up => Code::ArrowUp,
+        Right => Code::ArrowRight,
+        Down => Code::ArrowDown,
+        Back => Code::Backspace,
+        Return => Code::Enter,
+        Space => Code::Space,
+        Numlock => Code::NumLock,
+        Numpad0 => Code::Numpad0,
+        Numpad1 => Code::Numpad1,
+        Numpad2 => Code::Numpad2,
+        Numpad3 => Code::Numpad3,
+        Numpad4 => Code::Numpad4,
+        Numpad5 => Code::Numpad5,
+        Numpad6 => Code::Numpad6,
+        Numpad7 => Code::Numpad7,
+        Numpad8 => Code::Numpad8,
+        Numpad9 => Code::Numpad9,
+        NumpadAdd => Code::NumpadAdd,
+        NumpadDivide => Code::NumpadDivide,
+        NumpadDecimal => Code::NumpadDecimal,
+        NumpadComma => Code::NumpadComma,
+        NumpadEnter => Code::NumpadEnter,
+        NumpadEquals => Code::NumpadEqual,
+        NumpadMultiply => Code::NumpadMultiply,
+        NumpadSubtract => Code::NumpadSubtract,
+        Apostrophe => Code::Quote,
+        Apps => Code::ContextMenu,
+        Backslash => Code::Backslash,
+        Capital => Code::CapsLock,
+        Comma => Code::Comma,
+        Convert => Code::Convert,
+        Equals => Code::Equal,
+        Grave => Code::Backquote,
+        Kana => Code::KanaMode,
+        LAlt => Code::AltLeft,
+        LBracket => Code::BracketLeft,
+        LControl => Code::ControlLeft,
+        LShift => Code::ShiftLeft,
+        LWin => Code::MetaLeft,
+        Mail => Code::LaunchMail,
+        MediaSelect => Code::MediaSelect,
+        MediaStop => Code::MediaStop,
+        Minus => Code::Minus,
+        Mute => Code::AudioVolumeMute,
+        NavigateForward => Code::BrowserForward,
+        NavigateBackward => Code::BrowserBack,
+        NextTrack => Code::MediaTrackNext,
+        NoConvert => Code::NonConvert,
+        OEM102 => Code::IntlBackslash,
+        Period => Code::Period,
+        PlayPause => Code::MediaPlayPause,
+        Power => Code::Power,
+        PrevTrack => Code::MediaTrackPrevious,
+        RAlt => Code::AltRight,
+        RBracket => Code::BracketRight,
+        RControl => Code::ControlRight,
+        RShift => Code::ShiftRight,
+        RWin => Code::MetaRight,
+        Semicolon => Code::Semicolon,
+        Slash => Code::Slash,
+        Sleep => Code::Sleep,
+        Tab => Code::Tab,
+        VolumeDown => Code::AudioVolumeDown,
+        VolumeUp => Code::AudioVolumeUp,
+        Wake => Code::WakeUp,
+        WebBack => Code::BrowserBack,
+        WebFavorites => Code::BrowserFavorites,
+        WebForward => Code::BrowserForward,
+        WebHome => Code::BrowserHome,
+        WebRefresh => Code::BrowserRefresh,
+        WebSearch => Code::BrowserSearch,
+        WebStop => Code::BrowserStop,
+        Yen => Code::IntlYen,
+        Copy => Code::Copy,
+        Paste => Code::Paste,
+        Cut => Code::Cut,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Converts a winit [`ModifiersState`] to [`Modifiers`].
+pub fn modifiers_from_state(state: ModifiersState) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    if state.shift() {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if state.ctrl() {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if state.alt() {
+        modifiers |= Modifiers::ALT;
+    }
+    if state.logo() {
+        modifiers |= Modifiers::SUPER;
+    }
+    modifiers
+}
+
+/// Returns the [`Accelerator`] pressed by `input` while `modifiers` are held,
+/// or `None` for key releases and unknown keys.
+pub fn accelerator_from_keyboard_input(
+    input: &KeyboardInput,
+    modifiers: ModifiersState,
+) -> Option<Accelerator> {
+    if input.state != ElementState::Pressed {
+        return None;
+    }
+
+    let code = code_from_virtual_keycode(input.virtual_keycode?)?;
+    Some(Accelerator::new(
+        Some(modifiers_from_state(modifiers)),
+        code,
+    ))
+}
+
+/// Feeds winit window events to [`Menu::handle_key`], and [`WindowEvent::ReceivedCharacter`]
+/// to [`Menu::handle_logical_key`] for the accelerators created with [`Accelerator::new_logical`],
+/// so accelerators work even when the native menu of a window isn't focused or shown.
+///
+/// It keeps track of the modifiers from [`WindowEvent::ModifiersChanged`],
+/// so it should be given all the events of a window.
+///
+/// Some platforms report the characters typed while `Ctrl` is held as control characters,
+/// logical accelerators using `Ctrl` then don't match.
+#[derive(Debug, Default)]
+pub struct AcceleratorHandler {
+    modifiers: ModifiersState,
+    /// Whether the last key press activated an item, so its character doesn't activate one too.
+    handled_key: bool,
+}
+
+impl AcceleratorHandler {
+    /// Creates a new handler.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Activates the item of `menu` with the accelerator pressed in `event`,
+    /// and returns the emitted [`MenuEvent`].
+    pub fn handle_window_event(&mut self, menu: &Menu, event: &WindowEvent) -> Option<MenuEvent> {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = *modifiers;
+                None
+            }
+            WindowEvent::KeyboardInput { input, .. } => {
+                self.handled_key = false;
+                let accelerator = accelerator_from_keyboard_input(input, self.modifiers)?;
+                let event = menu.handle_key(accelerator.mods, accelerator.key);
+                self.handled_key = event.is_some();
+                event
+            }
+            WindowEvent::ReceivedCharacter(c) => {
+                if std::mem::take(&mut self.handled_key) || c.is_control() {
+                    return None;
+                }
+                menu.handle_logical_key(
+                    modifiers_from_state(self.modifiers),
+                    &Key::Character(c.to_string()),
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_code_from_virtual_keycode() {
+    assert_eq!(
+        code_from_virtual_keycode(VirtualKeyCode::A),
+        Some(Code::KeyA)
+    );
+    assert_eq!(
+        code_from_virtual_keycode(VirtualKeyCode::Return),
+        Some(Code::Enter)
+    );
+    assert_eq!(code_from_virtual_keycode(VirtualKeyCode::Compose), None);
+    assert_eq!(
+        modifiers_from_state(ModifiersState::CTRL | ModifiersState::SHIFT),
+        Modifiers::CONTROL | Modifiers::SHIFT
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_accelerator_handler() {
+    use crate::{MenuItem, Submenu};
+
+    let save = MenuItem::new("Save", true, "Ctrl+KeyS".parse().ok());
+    let zoom = MenuItem::new("Zoom In", true, Some(Accelerator::new_logical(None, '+')));
+    let file = Submenu::with_items("File", true, &[&save, &zoom]).unwrap();
+    let menu = Menu::with_items(&[&file]).unwrap();
+
+    let key_press = |virtual_keycode| WindowEvent::KeyboardInput {
+        device_id: unsafe { ::winit::event::DeviceId::dummy() },
+        input: KeyboardInput {
+            scancode: 0,
+            state: ElementState::Pressed,
+            virtual_keycode: Some(virtual_keycode),
+            modifiers: ModifiersState::empty(),
+        },
+        is_synthetic: false,
+    };
+
+    let mut handler = AcceleratorHandler::new();
+    assert!(handler
+        .handle_window_event(&menu, &key_press(VirtualKeyCode::S))
+        .is_none());
+    handler.handle_window_event(&menu, &WindowEvent::ModifiersChanged(ModifiersState::CTRL));
+    let event = handler.handle_window_event(&menu, &key_press(VirtualKeyCode::S));
+    assert_eq!(event.map(|e| e.id), Some(save.id()));
+
+    handler.handle_window_event(
+        &menu,
+        &WindowEvent::ModifiersChanged(ModifiersState::empty()),
+    );
+    assert!(handler
+        .handle_window_event(&menu, &key_press(VirtualKeyCode::Equals))
+        .is_none());
+    let event = handler.handle_window_event(&menu, &WindowEvent::ReceivedCharacter('+'));
+    assert_eq!(event.map(|e| e.id), Some(zoom.id()));
+}